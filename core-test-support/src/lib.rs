@@ -0,0 +1,197 @@
+//! In-process test support for `energy_core`'s `EnergyMonitor`, the crate-root
+//! async `HardwarePlugin` lineage (`hardware_plugins::HardwarePlugin`/`Measurement`).
+//! Mirrors `hardware-plugins-test-support`, which does the same for the separate MSR
+//! register-based `hardware_plugins::plugins` lineage.
+//!
+//! `EnergyMonitor::start_measurement`/`stop_measurement` only ever call a plugin's
+//! `get_measurement`, never `start_measurement`/`stop_measurement` on the plugin itself
+//! (see `energy_core::EnergyMonitor`), so `ScriptedPlugin` plays back one `Measurement`
+//! per `get_measurement` call rather than modeling a separate begin/end pair. This lets
+//! third-party plugin authors validate wraparound handling and multi-source aggregation
+//! against real `EnergyMonitor`/`MeasurementSession` code without real hardware and
+//! without hand-wiring a mock per test.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use energy_core::{EnergyMonitor, MeasurementSession};
+use hardware_plugins::{HardwareError, HardwarePlugin, Measurement};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// A `HardwarePlugin` that plays back a predetermined sequence of `Measurement`s, one
+/// per `get_measurement` call, instead of reading real hardware. Exhausting the script
+/// is a test bug (the harness called `get_measurement` more times than scripted), so it
+/// surfaces as a `HardwareError` rather than silently repeating the last value.
+pub struct ScriptedPlugin {
+    name: &'static str,
+    script: Mutex<VecDeque<Measurement>>,
+}
+
+impl ScriptedPlugin {
+    /// Play back exactly these measurements, in order, one per `get_measurement` call.
+    pub fn new(name: &'static str, script: Vec<Measurement>) -> Self {
+        Self {
+            name,
+            script: Mutex::new(script.into()),
+        }
+    }
+
+    /// Build a plugin that reports `steps` readings ramping linearly from
+    /// `start_joules` to `end_joules`, one second apart starting at `Utc::now()` —
+    /// enough to drive a `start_measurement`/`stop_measurement` pair (`steps >= 2`)
+    /// without hand-building a `Measurement` sequence.
+    pub fn linear_ramp(name: &'static str, start_joules: f64, end_joules: f64, steps: usize) -> Self {
+        assert!(steps >= 2, "a ramp needs at least a start and an end reading");
+        let now = Utc::now();
+        let script = (0..steps)
+            .map(|i| {
+                let t = i as f64 / (steps - 1) as f64;
+                Measurement {
+                    timestamp: now + chrono::Duration::seconds(i as i64),
+                    joules: start_joules + (end_joules - start_joules) * t,
+                    source: name.to_string(),
+                    max_joules: None,
+                }
+            })
+            .collect();
+        Self::new(name, script)
+    }
+
+    /// Build a plugin whose two scripted readings simulate a fixed-width hardware
+    /// counter (e.g. RAPL `energy_uj`) wrapping around exactly once between them, so
+    /// `BaseAdapter::calculate_energy_delta`'s wraparound branch can be exercised.
+    pub fn wrapping_counter(name: &'static str, start_joules: f64, end_joules: f64, max_joules: f64) -> Self {
+        assert!(
+            end_joules < start_joules,
+            "a wrapping counter's end reading must look smaller than its start reading"
+        );
+        let now = Utc::now();
+        Self::new(
+            name,
+            vec![
+                Measurement {
+                    timestamp: now,
+                    joules: start_joules,
+                    source: name.to_string(),
+                    max_joules: Some(max_joules),
+                },
+                Measurement {
+                    timestamp: now + chrono::Duration::seconds(1),
+                    joules: end_joules,
+                    source: name.to_string(),
+                    max_joules: Some(max_joules),
+                },
+            ],
+        )
+    }
+}
+
+#[async_trait]
+impl HardwarePlugin for ScriptedPlugin {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        "Scripted test plugin that plays back a predetermined measurement sequence"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn is_supported(&self) -> bool {
+        true
+    }
+
+    fn initialize(&mut self) -> Result<(), HardwareError> {
+        Ok(())
+    }
+
+    async fn start_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.get_measurement()
+    }
+
+    async fn stop_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.get_measurement()
+    }
+
+    fn get_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.script
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| HardwareError::Other(format!("ScriptedPlugin \"{}\" script exhausted", self.name)))
+    }
+
+    fn supported_metrics(&self) -> Vec<&'static str> {
+        vec!["energy"]
+    }
+
+    async fn get_total_energy_consumption(&self) -> Result<f64, HardwareError> {
+        Ok(0.0)
+    }
+}
+
+/// Drive a full `start_measurement` -> wait `between` -> `stop_measurement` cycle
+/// against a real `EnergyMonitor`, so a test can assert on the resulting
+/// `MeasurementSession` (duration, per-source deltas, total energy) in one call
+/// instead of re-wiring the two-step lifecycle every time.
+pub async fn run_session(monitor: &EnergyMonitor, between: Duration) -> MeasurementSession {
+    let session = monitor
+        .start_measurement()
+        .await
+        .expect("EnergyMonitor::start_measurement failed");
+    tokio::time::sleep(between).await;
+    monitor
+        .stop_measurement(session)
+        .await
+        .expect("EnergyMonitor::stop_measurement failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn scripted_plugin_drives_a_single_source_session() {
+        let mut monitor = EnergyMonitor::new();
+        monitor.register_plugin(Box::new(ScriptedPlugin::linear_ramp("test-source", 10.0, 15.0, 2)));
+
+        let session = run_session(&monitor, Duration::from_millis(10)).await;
+
+        assert!((session.total_energy - 5.0).abs() < 1e-9);
+        assert!(session.duration >= Duration::from_millis(10));
+    }
+
+    #[tokio::test]
+    async fn scripted_plugin_aggregates_multiple_sources() {
+        let mut monitor = EnergyMonitor::new();
+        monitor.register_plugin(Box::new(ScriptedPlugin::linear_ramp("package", 0.0, 2.0, 2)));
+        monitor.register_plugin(Box::new(ScriptedPlugin::linear_ramp("dram", 0.0, 0.5, 2)));
+
+        let session = run_session(&monitor, Duration::from_millis(1)).await;
+
+        let (package_start, package_end) = session.get_measurements("package").unwrap();
+        assert_eq!(package_start.joules, 0.0);
+        assert_eq!(package_end.joules, 2.0);
+        assert!((session.total_energy - 2.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn scripted_plugin_exercises_wraparound_handling() {
+        let mut monitor = EnergyMonitor::new();
+        monitor.register_plugin(Box::new(ScriptedPlugin::wrapping_counter(
+            "rapl-package",
+            260_000.0,
+            100.0,
+            260_000.0,
+        )));
+
+        let session = run_session(&monitor, Duration::from_millis(1)).await;
+
+        // Wrapped past 260_000.0 back to 100.0: travelled 100.0 counts past the wrap.
+        assert!((session.total_energy - 100.0).abs() < 1e-9);
+    }
+}
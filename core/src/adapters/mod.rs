@@ -1,5 +1,8 @@
+#[cfg(feature = "intel-rapl")]
 mod intel_rapl;
+#[cfg(feature = "arm-pmu")]
 mod arm_pmu;
+#[cfg(feature = "nvidia-gpu")]
 mod nvidia_gpu;
 
 use crate::{MeasurementSession, EnergyResult};
@@ -22,9 +25,20 @@ impl BaseAdapter {
         end.timestamp.signed_duration_since(start.timestamp)
     }
 
-    /// Calculate the energy delta between two measurements
+    /// Calculate the energy delta between two measurements, accounting for a
+    /// fixed-width hardware counter wrapping around during the session (e.g. a RAPL
+    /// `energy_uj` register). If `end.joules` looks smaller than `start.joules` and
+    /// either measurement declares a `max_joules` range, the counter is assumed to
+    /// have wrapped exactly once; without a known range the raw (possibly negative)
+    /// delta is returned, since the source isn't known to use a wrapping counter.
     pub fn calculate_energy_delta(start: &Measurement, end: &Measurement) -> f64 {
-        end.joules - start.joules
+        if end.joules >= start.joules {
+            return end.joules - start.joules;
+        }
+        match start.max_joules.or(end.max_joules) {
+            Some(max_joules) => (max_joules - start.joules) + end.joules,
+            None => end.joules - start.joules,
+        }
     }
 
     /// Create a measurement session from start and end measurements
@@ -43,6 +57,10 @@ impl BaseAdapter {
         MeasurementSession {
             start_measurements,
             end_measurements,
+            start_temperatures: std::collections::HashMap::new(),
+            end_temperatures: std::collections::HashMap::new(),
+            start_host_metrics: None,
+            end_host_metrics: None,
             start: start.timestamp,
             end: end.timestamp,
             duration: duration.to_std().unwrap_or_default(),
@@ -68,6 +86,9 @@ pub trait EnergyAdapter: Send + Sync {
     fn read_measurements(&self) -> EnergyResult<Vec<Measurement>>;
 }
 
+#[cfg(feature = "intel-rapl")]
 pub use intel_rapl::IntelRaplAdapter;
+#[cfg(feature = "arm-pmu")]
 pub use arm_pmu::ArmPmuAdapter;
+#[cfg(feature = "nvidia-gpu")]
 pub use nvidia_gpu::NvidiaGpuAdapter; 
\ No newline at end of file
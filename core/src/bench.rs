@@ -0,0 +1,246 @@
+//! `energy_bench`: a benchmarking harness that drives a workload at a controlled
+//! operations-per-second rate while `SamplingLogger` records energy in the background,
+//! modeled on windsock's local-run loop. Turns `calculate_metrics`/`compare_metrics`
+//! into an A/B energy regression tool: run two workload variants and diff the result.
+
+use crate::adapters::BaseAdapter;
+use crate::measurement::{SampleSeries, SamplingLogger, SamplingLoggerError};
+use crate::{EnergyError, EnergyResult};
+use hardware_plugins::HardwarePlugin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+impl From<SamplingLoggerError> for EnergyError {
+    fn from(err: SamplingLoggerError) -> Self {
+        EnergyError::MeasurementError(err.to_string())
+    }
+}
+
+/// A pluggable observer attached to a bench run by name, mirroring windsock's named
+/// profilers (e.g. `sys_monitor`, `metrics`).
+pub trait Profiler: Send + Sync {
+    /// Stable name under which this profiler's summary appears in
+    /// `BenchReport::profiler_summaries`.
+    fn name(&self) -> &'static str;
+
+    /// Called once the run's sample series is final; returns a human-readable summary.
+    fn summarize(&self, series: &SampleSeries) -> String;
+}
+
+/// Profiler that reports which sources were observed during the run. It sees only
+/// the energy sample series, so it records no CPU load or temperature.
+pub struct SysMonitorProfiler;
+
+impl Profiler for SysMonitorProfiler {
+    fn name(&self) -> &'static str {
+        "sys_monitor"
+    }
+
+    fn summarize(&self, series: &SampleSeries) -> String {
+        let sources: Vec<&str> = series.sources();
+        format!("sys_monitor: observed sources {:?}", sources)
+    }
+}
+
+/// Profiler that dumps the raw per-source sample series as-is, for post-hoc analysis.
+pub struct MetricsProfiler;
+
+impl Profiler for MetricsProfiler {
+    fn name(&self) -> &'static str {
+        "metrics"
+    }
+
+    fn summarize(&self, series: &SampleSeries) -> String {
+        let mut lines = Vec::new();
+        for source in series.sources() {
+            lines.push(format!(
+                "{}: {} samples",
+                source,
+                series.for_source(source).len()
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Configuration for a single `EnergyBench::run` invocation.
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    /// Target rate at which the workload closure is invoked.
+    pub target_ops_per_sec: f64,
+    /// How long the measured portion of the run lasts.
+    pub duration: Duration,
+    /// Optional warmup period, run at the same rate but excluded from measurement.
+    pub warmup: Option<Duration>,
+    /// Polling interval passed through to the underlying `SamplingLogger`.
+    pub sampling_interval: Duration,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            target_ops_per_sec: 10.0,
+            duration: Duration::from_secs(10),
+            warmup: Some(Duration::from_secs(1)),
+            sampling_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Result of a single bench run.
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub operations: u64,
+    pub total_joules: f64,
+    pub joules_per_operation: f64,
+    pub mean_watts: f64,
+    pub peak_watts: f64,
+    pub profiler_summaries: Vec<(String, String)>,
+}
+
+/// Percentage energy delta between two bench runs, with a rough confidence score
+/// derived from how far each run's peak watts sit from its mean watts (a tighter
+/// spread on both sides gives a higher score). The raw sample variance is not used.
+#[derive(Debug, Clone)]
+pub struct ComparisonReport {
+    pub baseline: BenchReport,
+    pub candidate: BenchReport,
+    pub energy_delta_percent: f64,
+    pub confidence: f64,
+}
+
+/// Drives a workload closure at a controlled rate while recording energy usage.
+pub struct EnergyBench {
+    plugins: Vec<Arc<dyn HardwarePlugin>>,
+    profilers: Vec<Box<dyn Profiler>>,
+}
+
+impl EnergyBench {
+    /// Create a bench harness sampling the given set of hardware plugins.
+    pub fn new(plugins: Vec<Arc<dyn HardwarePlugin>>) -> Self {
+        Self {
+            plugins,
+            profilers: Vec::new(),
+        }
+    }
+
+    /// Attach a profiler instance (e.g. `SysMonitorProfiler`, `MetricsProfiler`) that
+    /// runs once the sample series is final. There is no lookup of profilers by name.
+    pub fn with_profiler(mut self, profiler: Box<dyn Profiler>) -> Self {
+        self.profilers.push(profiler);
+        self
+    }
+
+    /// Drive `workload` at `target_ops_per_sec` for `duration`, returning the number of
+    /// invocations completed.
+    async fn drive_at_rate<F: FnMut()>(
+        workload: &mut F,
+        target_ops_per_sec: f64,
+        duration: Duration,
+    ) -> u64 {
+        let tick = Duration::from_secs_f64(1.0 / target_ops_per_sec.max(0.001));
+        let deadline = Instant::now() + duration;
+        let mut ops = 0u64;
+        let mut ticker = tokio::time::interval(tick);
+        while Instant::now() < deadline {
+            ticker.tick().await;
+            workload();
+            ops += 1;
+        }
+        ops
+    }
+
+    /// Run the bench: optional warmup (unmeasured) followed by the measured window,
+    /// sampled in the background by a `SamplingLogger`.
+    pub async fn run<F: FnMut()>(
+        &self,
+        config: BenchConfig,
+        mut workload: F,
+    ) -> EnergyResult<BenchReport> {
+        if let Some(warmup) = config.warmup {
+            Self::drive_at_rate(&mut workload, config.target_ops_per_sec, warmup).await;
+        }
+
+        let logger = SamplingLogger::new(self.plugins.clone(), config.sampling_interval)?;
+        let handle = logger.start()?;
+
+        let operations =
+            Self::drive_at_rate(&mut workload, config.target_ops_per_sec, config.duration).await;
+
+        let series = handle.stop().await;
+
+        let total_joules: f64 = series
+            .sources()
+            .iter()
+            .filter_map(|source| {
+                let samples = series.for_source(source);
+                let first = samples.first()?;
+                let last = samples.last()?;
+                Some(BaseAdapter::calculate_energy_delta(first, last))
+            })
+            .sum();
+
+        let mean_watts = series
+            .sources()
+            .iter()
+            .filter_map(|source| series.average_watts(source))
+            .sum();
+
+        let peak_watts = series
+            .sources()
+            .iter()
+            .filter_map(|source| series.peak_watts(source))
+            .fold(0.0_f64, f64::max);
+
+        let joules_per_operation = if operations > 0 {
+            total_joules / operations as f64
+        } else {
+            0.0
+        };
+
+        let profiler_summaries = self
+            .profilers
+            .iter()
+            .map(|p| (p.name().to_string(), p.summarize(&series)))
+            .collect();
+
+        Ok(BenchReport {
+            operations,
+            total_joules,
+            joules_per_operation,
+            mean_watts,
+            peak_watts,
+            profiler_summaries,
+        })
+    }
+
+    /// Compare two bench reports (e.g. baseline vs. candidate workload variant) and
+    /// report the percentage energy delta with a confidence score.
+    pub fn compare(baseline: BenchReport, candidate: BenchReport) -> ComparisonReport {
+        let energy_delta_percent = if baseline.total_joules.abs() > f64::EPSILON {
+            ((candidate.total_joules - baseline.total_joules) / baseline.total_joules) * 100.0
+        } else {
+            0.0
+        };
+
+        // Confidence heuristic: how far mean is from peak on both sides approximates
+        // sample variance without needing the raw series here; tighter spreads on
+        // both runs raise confidence in the delta.
+        let spread = |r: &BenchReport| {
+            if r.mean_watts.abs() > f64::EPSILON {
+                (r.peak_watts - r.mean_watts).abs() / r.mean_watts.abs()
+            } else {
+                1.0
+            }
+        };
+        let combined_spread = (spread(&baseline) + spread(&candidate)) / 2.0;
+        let confidence = (1.0 - combined_spread.min(1.0)).max(0.0);
+
+        ComparisonReport {
+            baseline,
+            candidate,
+            energy_delta_percent,
+            confidence,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Analysis/report generation for the "client" side of the host/client split
+//! described in the crate's module doc.
+//!
+//! `EnergyMonitor` (behind the `host` feature) only ever produces a `MeasurementSession`
+//! — raw measurements, nothing a user reads directly. This module is the other half:
+//! turning that session, plus the source it measured, into the two things a user
+//! actually wants — what's energy-expensive about the code (`optimizer`) and a
+//! renderable report of what was measured (`visualization`) — without a pure
+//! measurement daemon build ever needing to link either.
+
+use crate::{EnergyError, EnergyResult, MeasurementSession};
+use optimizer::OptimizationSuggestion;
+use visualization::reports::{generate_report, Measurement as ReportMeasurement, Report};
+
+/// Run `optimizer`'s static analysis + suggestion pipeline over `code` and fold
+/// `session`'s end measurements into a `visualization::reports::Report`, the pairing a
+/// client needs to show a user: what's slow, and what it cost.
+pub fn build_report(
+    code: &str,
+    language_id: &str,
+    session: &MeasurementSession,
+) -> EnergyResult<(Vec<OptimizationSuggestion>, Report)> {
+    let suggestions = optimizer::analyze_code(code, language_id)
+        .map_err(|e| EnergyError::MeasurementError(e.to_string()))?;
+
+    let measurements = session
+        .end_measurements
+        .iter()
+        .map(|(source, measurement)| ReportMeasurement {
+            name: source.clone(),
+            value: measurement.joules,
+            unit: "joules".to_string(),
+        })
+        .collect();
+
+    Ok((suggestions, generate_report(measurements)))
+}
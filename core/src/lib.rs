@@ -1,20 +1,60 @@
+//! Cargo features:
+//! - `host` (default): the live-capture path — `EnergyMonitor`, `ContinuousMonitor`,
+//!   and dynamic plugin loading (`plugin`) — that actually talks to hardware energy
+//!   counters through `hardware_plugins`. A measurement daemon needs this; a report
+//!   viewer doesn't.
+//! - `client` (default): the `client` module, which pulls in `optimizer` and
+//!   `visualization` to turn a `MeasurementSession` plus source code into suggestions
+//!   and a renderable report. An unprivileged analysis/report consumer can build with
+//!   `--no-default-features --features client` to leave out the live-capture path.
+//!   That build still depends on `hardware_plugins`: `MeasurementSession` and the
+//!   `adapters`, `measurement`, `bench` and `trace` modules use its types whatever the
+//!   features.
+
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use thiserror::Error;
-use hardware_plugins::{HardwarePlugin, PluginRegistry, HardwareError, Measurement};
+use hardware_plugins::{HardwareError, HostMetrics, Measurement, ThermalReading};
+#[cfg(feature = "host")]
+use hardware_plugins::{HardwarePlugin, HostMetricsPlugin, PluginRegistry, ThermalPlugin};
+#[cfg(feature = "host")]
+use hardware_plugins::common::PluginConfig;
+#[cfg(feature = "host")]
 use language_adapters::LanguageAdapter;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use trace::{FieldDef, TraceWriter};
 
 pub mod adapters;
+pub mod bench;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod measurement;
+#[cfg(feature = "host")]
 pub mod plugin;
+pub mod trace;
 
 /// Represents a measurement session with start and end measurements
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MeasurementSession {
     pub start_measurements: HashMap<String, Measurement>,
     pub end_measurements: HashMap<String, Measurement>,
+    /// Temperature readings taken alongside `start_measurements`, keyed by the same
+    /// source naming as a thermal plugin's `name()`. Optional: a session only has
+    /// entries here if at least one `ThermalPlugin` was registered on the `EnergyMonitor`
+    /// that produced it.
+    pub start_temperatures: HashMap<String, ThermalReading>,
+    /// Temperature readings taken alongside `end_measurements`. See `start_temperatures`.
+    pub end_temperatures: HashMap<String, ThermalReading>,
+    /// Host resource-utilization snapshot taken alongside `start_measurements`, so
+    /// `total_energy` can be normalized against what else the machine was doing (see
+    /// `energy_per_cpu_second`/`energy_per_byte`). `None` if the `EnergyMonitor` that
+    /// produced this session wasn't carrying a `HostMetricsPlugin`.
+    pub start_host_metrics: Option<HostMetrics>,
+    /// Host resource-utilization snapshot taken alongside `end_measurements`. See
+    /// `start_host_metrics`.
+    pub end_host_metrics: Option<HostMetrics>,
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
     pub duration: Duration,
@@ -27,6 +67,10 @@ impl MeasurementSession {
         Self {
             start_measurements: HashMap::new(),
             end_measurements: HashMap::new(),
+            start_temperatures: HashMap::new(),
+            end_temperatures: HashMap::new(),
+            start_host_metrics: None,
+            end_host_metrics: None,
             start: Utc::now(),
             end: Utc::now(),
             duration: Duration::from_secs(0),
@@ -48,12 +92,90 @@ impl MeasurementSession {
         self.calculate_total_energy();
     }
 
-    /// Calculate total energy consumption
+    /// Record the temperature at the start of the session for a given sensor, so it
+    /// can later be correlated against the energy spike it was measured alongside.
+    pub fn add_start_temperature(&mut self, source: String, reading: ThermalReading) {
+        self.start_temperatures.insert(source, reading);
+    }
+
+    /// Record the temperature at the end of the session for a given sensor.
+    pub fn add_end_temperature(&mut self, source: String, reading: ThermalReading) {
+        self.end_temperatures.insert(source, reading);
+    }
+
+    /// Get the start/end temperature readings for a specific sensor, if both were
+    /// recorded.
+    pub fn get_temperatures(&self, source: &str) -> Option<(ThermalReading, ThermalReading)> {
+        let start = self.start_temperatures.get(source)?;
+        let end = self.end_temperatures.get(source)?;
+        Some((start.clone(), end.clone()))
+    }
+
+    /// Record the host resource-utilization snapshot taken at the start of the session.
+    pub fn add_start_host_metrics(&mut self, metrics: HostMetrics) {
+        self.start_host_metrics = Some(metrics);
+    }
+
+    /// Record the host resource-utilization snapshot taken at the end of the session.
+    pub fn add_end_host_metrics(&mut self, metrics: HostMetrics) {
+        self.end_host_metrics = Some(metrics);
+    }
+
+    /// Average CPU-seconds consumed across all cores during this session, estimated by
+    /// integrating the end-of-session per-core utilization over `duration`. `None` if
+    /// `end_host_metrics` wasn't recorded.
+    pub fn cpu_seconds(&self) -> Option<f64> {
+        let end = self.end_host_metrics.as_ref()?;
+        if end.cpu_utilization_percent.is_empty() {
+            return Some(0.0);
+        }
+        let total_utilization: f64 = end.cpu_utilization_percent.iter().sum::<f64>() / 100.0;
+        Some(total_utilization * self.duration.as_secs_f64())
+    }
+
+    /// Total network bytes transferred (received plus transmitted, summed across every
+    /// interface) between the start and end of this session. `None` unless both
+    /// `start_host_metrics` and `end_host_metrics` were recorded.
+    pub fn network_bytes_transferred(&self) -> Option<u64> {
+        let start = self.start_host_metrics.as_ref()?;
+        let end = self.end_host_metrics.as_ref()?;
+        let mut total = 0u64;
+        for (interface, (end_rx, end_tx)) in &end.network_bytes {
+            let (start_rx, start_tx) = start.network_bytes.get(interface).copied().unwrap_or((0, 0));
+            total += end_rx.saturating_sub(start_rx) + end_tx.saturating_sub(start_tx);
+        }
+        Some(total)
+    }
+
+    /// `total_energy` normalized by `cpu_seconds`, for comparing workloads independent
+    /// of how long or how CPU-intensive each one ran. `None` if `cpu_seconds` is
+    /// unavailable or zero.
+    pub fn energy_per_cpu_second(&self) -> Option<f64> {
+        match self.cpu_seconds() {
+            Some(cpu_seconds) if cpu_seconds > 0.0 => Some(self.total_energy / cpu_seconds),
+            _ => None,
+        }
+    }
+
+    /// `total_energy` normalized by `network_bytes_transferred`. `None` if that's
+    /// unavailable or zero.
+    pub fn energy_per_byte(&self) -> Option<f64> {
+        match self.network_bytes_transferred() {
+            Some(bytes) if bytes > 0 => Some(self.total_energy / bytes as f64),
+            _ => None,
+        }
+    }
+
+    /// Calculate total energy consumption. Each source's delta is computed
+    /// independently via `BaseAdapter::calculate_energy_delta`, so a multi-domain
+    /// session (package, core, dram, ...) unwraps each domain's counter on its own
+    /// before the per-source deltas are summed.
     fn calculate_total_energy(&mut self) {
         self.total_energy = 0.0;
         for (source, end_measurement) in &self.end_measurements {
             if let Some(start_measurement) = self.start_measurements.get(source) {
-                self.total_energy += end_measurement.joules - start_measurement.joules;
+                self.total_energy +=
+                    crate::adapters::BaseAdapter::calculate_energy_delta(start_measurement, end_measurement);
             }
         }
     }
@@ -85,6 +207,8 @@ pub enum EnergyError {
     LanguageError(String),
     #[error("Measurement error: {0}")]
     MeasurementError(String),
+    #[error("Plugin error: {0}")]
+    PluginError(String),
 }
 
 impl From<String> for EnergyError {
@@ -97,11 +221,13 @@ impl From<String> for EnergyError {
 pub type EnergyResult<T> = Result<T, EnergyError>;
 
 /// Core measurement engine that manages hardware plugins and language adapters
+#[cfg(feature = "host")]
 pub struct MeasurementEngine {
     plugin_registry: PluginRegistry,
     language_adapters: Vec<Box<dyn LanguageAdapter>>,
 }
 
+#[cfg(feature = "host")]
 impl MeasurementEngine {
     /// Create a new measurement engine
     pub fn new() -> Self {
@@ -136,14 +262,134 @@ impl MeasurementEngine {
     }
 }
 
+/// Minimum interval `EnergyMonitor::start_sampling` accepts. Guards against a caller
+/// accidentally busy-polling a hardware energy counter (e.g. RAPL) on every tick.
+#[cfg(feature = "host")]
+pub const MIN_SAMPLING_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Default cap on how many samples a `ContinuousMonitor`'s ring buffer retains,
+/// bounding its memory footprint for long-running sessions.
+#[cfg(feature = "host")]
+pub const DEFAULT_MAX_SAMPLES: usize = 10_000;
+
+/// One reading of every available plugin taken on a single sampling tick.
+#[cfg(feature = "host")]
+#[derive(Debug, Clone)]
+pub struct PowerSample {
+    pub timestamp: DateTime<Utc>,
+    pub measurements: HashMap<String, Measurement>,
+}
+
+/// A running continuous sampling session returned by `EnergyMonitor::start_sampling`.
+/// Call `tick` in a loop to pull one sample at a time — an async stream of
+/// `PowerSample`s — then `stop` to fold everything collected so far into a
+/// `MeasurementSession`.
+#[cfg(feature = "host")]
+pub struct ContinuousMonitor<'a> {
+    monitor: &'a EnergyMonitor,
+    ticker: tokio::time::Interval,
+    samples: VecDeque<PowerSample>,
+    max_samples: usize,
+}
+
+#[cfg(feature = "host")]
+impl<'a> ContinuousMonitor<'a> {
+    /// Wait for the next tick, read every available plugin, and push the resulting
+    /// sample into the ring buffer, evicting the oldest sample first once
+    /// `max_samples` has been reached.
+    pub async fn tick(&mut self) -> EnergyResult<PowerSample> {
+        self.ticker.tick().await;
+
+        let mut measurements = HashMap::new();
+        for plugin in self.monitor.registry.get_available_plugins() {
+            if let Ok(measurement) = plugin.get_measurement() {
+                measurements.insert(plugin.name().to_string(), measurement);
+            }
+        }
+        let sample = PowerSample {
+            timestamp: Utc::now(),
+            measurements,
+        };
+
+        if self.samples.len() >= self.max_samples {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample.clone());
+
+        Ok(sample)
+    }
+
+    /// Samples collected so far.
+    pub fn samples(&self) -> &VecDeque<PowerSample> {
+        &self.samples
+    }
+
+    /// Fold every retained sample into a `MeasurementSession`. Each source's total is
+    /// the sum of its tick-to-tick `BaseAdapter::calculate_energy_delta`s — the
+    /// integral of the power samples over the session — rather than a single
+    /// start/end delta, so a session spanning several counter wraps still adds up
+    /// correctly (a plain start/end delta can only safely assume one wrap).
+    pub fn stop(self) -> MeasurementSession {
+        let mut session = MeasurementSession::new();
+
+        if let Some(first) = self.samples.front() {
+            session.start = first.timestamp;
+            session.start_measurements = first.measurements.clone();
+        }
+        if let Some(last) = self.samples.back() {
+            session.end = last.timestamp;
+            session.end_measurements = last.measurements.clone();
+        }
+        session.duration = session
+            .end
+            .signed_duration_since(session.start)
+            .to_std()
+            .unwrap_or_default();
+
+        let samples: Vec<&PowerSample> = self.samples.iter().collect();
+        let mut total_energy = 0.0;
+        for pair in samples.windows(2) {
+            let (prev, next) = (pair[0], pair[1]);
+            for (source, next_measurement) in &next.measurements {
+                if let Some(prev_measurement) = prev.measurements.get(source) {
+                    total_energy +=
+                        adapters::BaseAdapter::calculate_energy_delta(prev_measurement, next_measurement);
+                }
+            }
+        }
+        session.total_energy = total_energy;
+
+        session
+    }
+}
+
+#[cfg(feature = "host")]
 pub struct EnergyMonitor {
     registry: PluginRegistry,
+    thermal_plugins: Vec<Box<dyn ThermalPlugin>>,
+    host_metrics: HostMetricsPlugin,
 }
 
+#[cfg(feature = "host")]
 impl EnergyMonitor {
     pub fn new() -> Self {
         Self {
             registry: PluginRegistry::new(),
+            thermal_plugins: Vec::new(),
+            host_metrics: HostMetricsPlugin::new(),
+        }
+    }
+
+    /// Build a monitor whose registry only holds plugins that actually probed as
+    /// supported on this host (see `PluginRegistry::detect_available`), rather than
+    /// every compiled-in plugin regardless of whether its hardware is present. Pair
+    /// with `capabilities()` so a caller (e.g. the VSCode integration) can report which
+    /// energy sources are live instead of silently measuring nothing.
+    pub fn detect_available() -> Self {
+        Self {
+            registry: PluginRegistry::detect_available(),
+            thermal_plugins: Vec::new(),
+            host_metrics: HostMetricsPlugin::new(),
         }
     }
 
@@ -151,6 +397,19 @@ impl EnergyMonitor {
         self.registry.register_plugin(plugin);
     }
 
+    /// Names of the energy sources this monitor's registry actually has registered.
+    /// Most meaningful on a monitor built via `detect_available`, where every
+    /// registered plugin already passed `is_supported()` on this host.
+    pub fn capabilities(&self) -> Vec<&'static str> {
+        self.registry.capabilities()
+    }
+
+    /// Register a temperature sensor to sample alongside every energy measurement, so
+    /// a session's start/end energy can be correlated against die temperature.
+    pub fn register_thermal_plugin(&mut self, plugin: Box<dyn ThermalPlugin>) {
+        self.thermal_plugins.push(plugin);
+    }
+
     pub async fn start_measurement(&self) -> EnergyResult<MeasurementSession> {
         let plugins = self.registry.get_plugins();
         let mut session = MeasurementSession::new();
@@ -158,6 +417,14 @@ impl EnergyMonitor {
             let measurement = plugin.get_measurement().map_err(EnergyError::HardwareError)?;
             session.add_start_measurement(plugin.name().to_string(), measurement);
         }
+        for thermal in self.available_thermal_plugins() {
+            if let Ok(reading) = thermal.get_reading() {
+                session.add_start_temperature(thermal.name().to_string(), reading);
+            }
+        }
+        if let Ok(metrics) = self.host_metrics.get_host_metrics() {
+            session.add_start_host_metrics(metrics);
+        }
         Ok(session)
     }
 
@@ -167,10 +434,95 @@ impl EnergyMonitor {
             let measurement = plugin.get_measurement().map_err(EnergyError::HardwareError)?;
             session.add_end_measurement(plugin.name().to_string(), measurement);
         }
+        for thermal in self.available_thermal_plugins() {
+            if let Ok(reading) = thermal.get_reading() {
+                session.add_end_temperature(thermal.name().to_string(), reading);
+            }
+        }
+        if let Ok(metrics) = self.host_metrics.get_host_metrics() {
+            session.add_end_host_metrics(metrics);
+        }
         Ok(session)
     }
 
+    /// Registered thermal plugins that are actually available on this host.
+    fn available_thermal_plugins(&self) -> impl Iterator<Item = &dyn ThermalPlugin> {
+        self.thermal_plugins
+            .iter()
+            .filter(|p| p.is_available())
+            .map(|p| p.as_ref())
+    }
+
     pub fn get_plugins(&self) -> Vec<&dyn HardwarePlugin> {
         self.registry.get_available_plugins()
     }
+
+    /// Start a continuous periodic sampling session: every `interval`, read every
+    /// available plugin and push the readings into the returned `ContinuousMonitor`'s
+    /// bounded ring buffer (`max_samples`, oldest evicted first). Unlike
+    /// `start_measurement`/`stop_measurement`, which only ever keep a single window,
+    /// or `record_trace`, which writes to a binary trace, this keeps samples in
+    /// memory as `PowerSample`s so `ContinuousMonitor::stop` can fold them straight
+    /// into a `MeasurementSession`.
+    pub fn start_sampling(
+        &self,
+        interval: Duration,
+        max_samples: usize,
+    ) -> EnergyResult<ContinuousMonitor<'_>> {
+        if interval < MIN_SAMPLING_INTERVAL {
+            return Err(EnergyError::MeasurementError(format!(
+                "sampling interval {:?} is below the minimum of {:?}",
+                interval, MIN_SAMPLING_INTERVAL
+            )));
+        }
+
+        Ok(ContinuousMonitor {
+            monitor: self,
+            ticker: tokio::time::interval(interval),
+            samples: VecDeque::with_capacity(max_samples.min(1024)),
+            max_samples: max_samples.max(1),
+        })
+    }
+
+    /// Stream a continuous time series of measurements from every available plugin to
+    /// `writer` for `duration`, sampling at `config.sampling_interval_ms`. Unlike
+    /// `start_measurement`/`stop_measurement`, which only ever keep a single window,
+    /// this records every intermediate sample to a compact binary trace (see
+    /// `crate::trace`) suitable for long-running profiling.
+    pub async fn record_trace<W: Write>(
+        &self,
+        config: &PluginConfig,
+        writer: W,
+        duration: std::time::Duration,
+    ) -> EnergyResult<TraceWriter<W>> {
+        let plugins = self.registry.get_available_plugins();
+        let fields: Vec<FieldDef> = plugins
+            .iter()
+            .map(|p| FieldDef::joules(p.name()))
+            .collect();
+
+        let mut trace = TraceWriter::new(writer, fields)
+            .map_err(|e| EnergyError::MeasurementError(e.to_string()))?;
+
+        let interval = std::time::Duration::from_millis(config.sampling_interval_ms.max(1));
+        let mut ticker = tokio::time::interval(interval);
+        let deadline = std::time::Instant::now() + duration;
+
+        while std::time::Instant::now() < deadline {
+            ticker.tick().await;
+            let timestamp_ms = Utc::now().timestamp_millis();
+            let values: Vec<(usize, f64)> = plugins
+                .iter()
+                .enumerate()
+                .filter_map(|(index, plugin)| {
+                    plugin.get_measurement().ok().map(|m| (index, m.joules))
+                })
+                .collect();
+            trace
+                .write_frame(timestamp_ms, &values)
+                .map_err(|e| EnergyError::MeasurementError(e.to_string()))?;
+        }
+
+        Ok(trace)
+    }
 } 
\ No newline at end of file
@@ -1,6 +1,10 @@
 use crate::{EnergyMeasurement, EnergyResult, MeasurementSession};
+use hardware_plugins::{HardwarePlugin, Measurement};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 
 /// A measurement session that tracks multiple energy sources
 pub struct MultiSourceSession {
@@ -87,4 +91,193 @@ impl MultiSourceSession {
             })
             .collect()
     }
-} 
\ No newline at end of file
+}
+
+/// Minimum polling interval `SamplingLogger` will accept. Anything shorter risks the
+/// sampling task itself perturbing the measurement it's trying to take.
+const MIN_SAMPLING_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Maximum number of `SamplingLogger` instances that may be running at once, to bound
+/// the total memory held by accumulated per-source time series.
+const MAX_CONCURRENT_LOGGERS: usize = 16;
+
+/// Tracks how many `SamplingLogger`s are currently active, process-wide.
+static ACTIVE_LOGGERS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Errors returned when configuring or starting a `SamplingLogger`.
+#[derive(Debug, thiserror::Error)]
+pub enum SamplingLoggerError {
+    #[error("sampling interval {0:?} is below the minimum of {MIN_SAMPLING_INTERVAL:?}")]
+    IntervalTooShort(Duration),
+    #[error("too many concurrent sampling loggers (limit is {MAX_CONCURRENT_LOGGERS})")]
+    TooManyLoggers,
+}
+
+/// A sink that receives each sample as it is collected, e.g. to forward it into
+/// `instrumentation::metrics::MetricsStore::record_measurement` for live dashboards.
+#[async_trait::async_trait]
+pub trait SampleSink: Send + Sync {
+    async fn on_sample(&self, measurement: &Measurement);
+}
+
+/// Per-source accumulated time series collected by a `SamplingLogger`.
+#[derive(Debug, Default, Clone)]
+pub struct SampleSeries {
+    samples: HashMap<String, Vec<Measurement>>,
+}
+
+impl SampleSeries {
+    fn push(&mut self, measurement: Measurement) {
+        self.samples
+            .entry(measurement.source.clone())
+            .or_insert_with(Vec::new)
+            .push(measurement);
+    }
+
+    /// All samples recorded for a given source, in collection order.
+    pub fn for_source(&self, source: &str) -> &[Measurement] {
+        self.samples.get(source).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// All source names with at least one sample.
+    pub fn sources(&self) -> Vec<&str> {
+        self.samples.keys().map(|s| s.as_str()).collect()
+    }
+
+    /// Maximum instantaneous power (W) observed for a source, derived from the
+    /// energy delta between consecutive samples divided by their time gap.
+    /// The value is only returned; `EnergyMetrics::peak_consumption` is not set from it.
+    pub fn peak_watts(&self, source: &str) -> Option<f64> {
+        let series = self.samples.get(source)?;
+        series
+            .windows(2)
+            .filter_map(|pair| {
+                let [a, b] = pair else { return None };
+                let dt = (b.timestamp - a.timestamp).num_milliseconds();
+                if dt <= 0 {
+                    return None;
+                }
+                Some((b.joules - a.joules) / (dt as f64 / 1000.0))
+            })
+            .fold(None, |max, watts| Some(max.map_or(watts, |m: f64| m.max(watts))))
+    }
+
+    /// Average power (W) for a source computed as the true integral of samples
+    /// (total energy delta across the series divided by elapsed wall time),
+    /// rather than just the two session endpoints.
+    pub fn average_watts(&self, source: &str) -> Option<f64> {
+        let series = self.samples.get(source)?;
+        let first = series.first()?;
+        let last = series.last()?;
+        let elapsed_secs = (last.timestamp - first.timestamp).num_milliseconds() as f64 / 1000.0;
+        if elapsed_secs <= 0.0 {
+            return None;
+        }
+        Some((last.joules - first.joules) / elapsed_secs)
+    }
+}
+
+/// A handle used to stop a running `SamplingLogger` before its fixed duration elapses.
+pub struct SamplingLoggerHandle {
+    stop: Arc<tokio::sync::Notify>,
+    task: JoinHandle<()>,
+    series: Arc<RwLock<SampleSeries>>,
+}
+
+impl SamplingLoggerHandle {
+    /// Signal the background sampling task to stop and wait for it to finish.
+    pub async fn stop(self) -> SampleSeries {
+        self.stop.notify_one();
+        let _ = self.task.await;
+        self.series.read().await.clone()
+    }
+
+    /// Snapshot the series accumulated so far without stopping collection.
+    pub async fn snapshot(&self) -> SampleSeries {
+        self.series.read().await.clone()
+    }
+}
+
+/// Periodically samples a set of `HardwarePlugin`s on a fixed interval and accumulates
+/// a per-source time series, so callers get peak power and transient behavior rather
+/// than a single start/end pair.
+pub struct SamplingLogger {
+    plugins: Vec<Arc<dyn HardwarePlugin>>,
+    interval: Duration,
+    sinks: Vec<Arc<dyn SampleSink>>,
+}
+
+impl SamplingLogger {
+    /// Create a new logger over the given plugins, polling every `interval`.
+    ///
+    /// Rejects intervals below `MIN_SAMPLING_INTERVAL` to avoid the sampling loop
+    /// perturbing the measurement it's taking.
+    pub fn new(
+        plugins: Vec<Arc<dyn HardwarePlugin>>,
+        interval: Duration,
+    ) -> Result<Self, SamplingLoggerError> {
+        if interval < MIN_SAMPLING_INTERVAL {
+            return Err(SamplingLoggerError::IntervalTooShort(interval));
+        }
+        Ok(Self {
+            plugins,
+            interval,
+            sinks: Vec::new(),
+        })
+    }
+
+    /// Attach a sink that every collected sample is streamed into as it arrives.
+    pub fn with_sink(mut self, sink: Arc<dyn SampleSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Start sampling in the background and run for a fixed duration, then stop
+    /// automatically and return the accumulated series.
+    pub async fn run_for(self, duration: Duration) -> Result<SampleSeries, SamplingLoggerError> {
+        let handle = self.start()?;
+        tokio::time::sleep(duration).await;
+        Ok(handle.stop().await)
+    }
+
+    /// Start sampling in the background, returning a handle that can be used to stop
+    /// it explicitly (e.g. for an open-ended measurement session).
+    pub fn start(self) -> Result<SamplingLoggerHandle, SamplingLoggerError> {
+        use std::sync::atomic::Ordering;
+
+        if ACTIVE_LOGGERS.fetch_add(1, Ordering::SeqCst) >= MAX_CONCURRENT_LOGGERS {
+            ACTIVE_LOGGERS.fetch_sub(1, Ordering::SeqCst);
+            return Err(SamplingLoggerError::TooManyLoggers);
+        }
+
+        let series = Arc::new(RwLock::new(SampleSeries::default()));
+        let stop = Arc::new(tokio::sync::Notify::new());
+        let plugins = self.plugins;
+        let sinks = self.sinks;
+        let interval = self.interval;
+        let series_task = Arc::clone(&series);
+        let stop_task = Arc::clone(&stop);
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        for plugin in &plugins {
+                            if let Ok(measurement) = plugin.get_measurement() {
+                                for sink in &sinks {
+                                    sink.on_sample(&measurement).await;
+                                }
+                                series_task.write().await.push(measurement);
+                            }
+                        }
+                    }
+                    _ = stop_task.notified() => break,
+                }
+            }
+            ACTIVE_LOGGERS.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        Ok(SamplingLoggerHandle { stop, task, series })
+    }
+}
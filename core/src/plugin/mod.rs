@@ -1,9 +1,83 @@
 use crate::adapters::EnergyAdapter;
-use crate::EnergyResult;
-use hardware_plugins::HardwarePlugin;
+use crate::{EnergyError, EnergyResult};
+use hardware_plugins::{HardwarePlugin, Measurement};
+use libloading::{Library, Symbol};
 use std::any::Any;
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
 use std::path::Path;
-use std::sync::Arc;
+use thiserror::Error;
+
+/// ABI version this build of core speaks. A dynamically loaded plugin whose
+/// `FfiPluginMetadata::abi_version` doesn't match is rejected rather than loaded,
+/// since its `PluginRegistration` layout or constructor contract may differ from
+/// this one. Bump this whenever either changes incompatibly.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// The well-known exported symbol every plugin shared library (`.so`/`.dylib`/`.dll`)
+/// must provide, null-terminated as `libloading::Library::get` expects.
+pub const PLUGIN_ENTRY_POINT: &[u8] = b"_codegreen_plugin_register\0";
+
+/// C-ABI-safe plugin metadata returned by a plugin's registration entry point. The
+/// string fields are null-terminated C strings owned by the plugin's shared library,
+/// valid for as long as that library stays loaded.
+#[repr(C)]
+pub struct FfiPluginMetadata {
+    pub abi_version: u32,
+    pub name: *const c_char,
+    pub version: *const c_char,
+    pub description: *const c_char,
+    pub author: *const c_char,
+}
+
+impl FfiPluginMetadata {
+    /// Copy the C strings out into an owned, safe `PluginMetadata`.
+    ///
+    /// # Safety
+    /// Every pointer field must be a valid, null-terminated C string for the duration
+    /// of this call. Guaranteed by the plugin entry point contract: the strings must
+    /// remain valid for as long as the plugin's library stays loaded, which outlives
+    /// this call.
+    unsafe fn to_owned(&self) -> PluginMetadata {
+        let read = |ptr: *const c_char| CStr::from_ptr(ptr).to_string_lossy().into_owned();
+        PluginMetadata {
+            name: read(self.name),
+            version: read(self.version),
+            description: read(self.description),
+            author: read(self.author),
+        }
+    }
+}
+
+/// Constructs the plugin's `Box<dyn HardwarePlugin>`. Trait object pointers are fat
+/// (data pointer + vtable pointer) and aren't part of the C ABI, so the plugin boxes
+/// the trait object a second time and returns a thin pointer to that box; the host
+/// reconstructs both layers on this side (see `PluginManager::load_plugin`).
+pub type PluginConstructorFn = unsafe extern "C" fn() -> *mut c_void;
+
+/// What a plugin's entry point returns: its metadata plus a constructor for its
+/// `HardwarePlugin` implementation.
+#[repr(C)]
+pub struct PluginRegistration {
+    pub metadata: FfiPluginMetadata,
+    pub construct: PluginConstructorFn,
+}
+
+/// Signature of the exported `_codegreen_plugin_register` symbol every plugin must provide.
+pub type PluginRegisterFn = unsafe extern "C" fn() -> PluginRegistration;
+
+/// Errors specific to loading a dynamic plugin library.
+#[derive(Debug, Error)]
+pub enum PluginLoadError {
+    #[error("failed to load plugin library: {0}")]
+    Library(#[from] libloading::Error),
+    #[error("plugin is missing the `{0}` entry point")]
+    MissingEntryPoint(String),
+    #[error("plugin ABI version {found} is incompatible with this build's ABI version {expected}")]
+    IncompatibleAbi { found: u32, expected: u32 },
+    #[error("a plugin named `{0}` is already registered")]
+    DuplicateName(String),
+}
 
 /// Plugin metadata
 #[derive(Debug, Clone)]
@@ -33,6 +107,34 @@ pub trait Plugin: Send + Sync {
     fn get_data(&self) -> Option<&dyn Any>;
 }
 
+/// Adapter backing a dynamically loaded `HardwarePlugin`. Holds the `Library` handle
+/// alongside the plugin so the shared object stays mapped for as long as the plugin's
+/// code and vtable are in use; dropping `_library` before `plugin` would leave
+/// `plugin`'s vtable pointing into unmapped memory.
+struct DynamicAdapter {
+    plugin: Box<dyn HardwarePlugin>,
+    _library: Library,
+}
+
+impl EnergyAdapter for DynamicAdapter {
+    fn name(&self) -> &str {
+        self.plugin.name()
+    }
+
+    fn initialize(&mut self) -> EnergyResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> EnergyResult<()> {
+        Ok(())
+    }
+
+    fn read_measurements(&self) -> EnergyResult<Vec<Measurement>> {
+        let measurement = self.plugin.get_measurement()?;
+        Ok(vec![measurement])
+    }
+}
+
 /// Plugin manager that handles loading and managing plugins
 pub struct PluginManager {
     adapters: Vec<Box<dyn EnergyAdapter>>,
@@ -46,14 +148,66 @@ impl PluginManager {
         }
     }
 
-    /// Load a plugin from a shared library
-    pub fn load_plugin(&mut self, _path: &Path) -> EnergyResult<()> {
-        // TODO: Implement dynamic plugin loading
-        // This would involve:
-        // 1. Loading the shared library
-        // 2. Finding the plugin entry point
-        // 3. Creating a plugin instance
-        // 4. Adding it to the plugins list
+    /// Load a plugin from a shared library, turning a hardcoded registry into a real
+    /// extension point: third parties can ship a new energy source (AMD RAPL, Apple
+    /// SoC power sensors, an RTL power meter, ...) as a `.so`/`.dylib` that exports
+    /// `_codegreen_plugin_register` without recompiling `core`.
+    ///
+    /// The library must export `_codegreen_plugin_register` matching
+    /// `PluginRegisterFn`; its `FfiPluginMetadata::abi_version` is checked against
+    /// `PLUGIN_ABI_VERSION` before anything else runs, so an incompatible plugin is
+    /// rejected rather than loaded.
+    pub fn load_plugin(&mut self, path: &Path) -> EnergyResult<()> {
+        let library = unsafe { Library::new(path) }
+            .map_err(|e| EnergyError::PluginError(PluginLoadError::from(e).to_string()))?;
+
+        let register: Symbol<PluginRegisterFn> = unsafe { library.get(PLUGIN_ENTRY_POINT) }
+            .map_err(|_| {
+                EnergyError::PluginError(
+                    PluginLoadError::MissingEntryPoint(
+                        String::from_utf8_lossy(PLUGIN_ENTRY_POINT.trim_end_matches(&[0u8][..]))
+                            .into_owned(),
+                    )
+                    .to_string(),
+                )
+            })?;
+
+        // Safety: `register` resolves to a valid `PluginRegisterFn` per the entry
+        // point contract, and the call itself has no preconditions beyond that.
+        let registration = unsafe { register() };
+
+        if registration.metadata.abi_version != PLUGIN_ABI_VERSION {
+            return Err(EnergyError::PluginError(
+                PluginLoadError::IncompatibleAbi {
+                    found: registration.metadata.abi_version,
+                    expected: PLUGIN_ABI_VERSION,
+                }
+                .to_string(),
+            ));
+        }
+
+        // Safety: see `FfiPluginMetadata::to_owned`.
+        let metadata = unsafe { registration.metadata.to_owned() };
+
+        if self.adapters.iter().any(|a| a.name() == metadata.name) {
+            return Err(EnergyError::PluginError(
+                PluginLoadError::DuplicateName(metadata.name).to_string(),
+            ));
+        }
+
+        // Safety: `construct` is the constructor declared by this same registration,
+        // returning a thin pointer to a double-boxed `Box<dyn HardwarePlugin>` per the
+        // `PluginConstructorFn` contract.
+        let raw = unsafe { (registration.construct)() };
+        let boxed: Box<Box<dyn HardwarePlugin>> =
+            unsafe { Box::from_raw(raw as *mut Box<dyn HardwarePlugin>) };
+        let plugin: Box<dyn HardwarePlugin> = *boxed;
+
+        self.adapters.push(Box::new(DynamicAdapter {
+            plugin,
+            _library: library,
+        }));
+
         Ok(())
     }
 
@@ -72,14 +226,17 @@ impl PluginManager {
         // Since we can't clone the trait object directly, we need to create a new adapter
         // based on the adapter's name and type
         match adapter.name() {
+            #[cfg(feature = "intel-rapl")]
             "intel_rapl" => {
                 let plugin = hardware_plugins::IntelRaplPlugin::new()?;
                 Ok(Box::new(crate::adapters::IntelRaplAdapter::new(Box::new(plugin))))
             }
+            #[cfg(feature = "arm-pmu")]
             "arm_pmu" => {
                 let plugin = hardware_plugins::ArmEnergyPlugin::new()?;
                 Ok(Box::new(crate::adapters::ArmPmuAdapter::new(Box::new(plugin))))
             }
+            #[cfg(feature = "nvidia-gpu")]
             "nvidia_gpu" => {
                 let plugin = hardware_plugins::NvidiaGpuPlugin::new()?;
                 Ok(Box::new(crate::adapters::NvidiaGpuAdapter::new(Box::new(plugin))))
@@ -91,4 +248,4 @@ impl PluginManager {
             ))
         }
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,360 @@
+//! Compact binary trace format for continuous energy logging.
+//!
+//! `EnergyMonitor::start_measurement`/`stop_measurement` only capture a single window
+//! and throw away everything sampled in between. `TraceWriter`/`TraceReader` instead
+//! stream a full time series of `Measurement`s to a blackbox-log-style binary format:
+//! a header describing each field (plugin, metric, unit, and a predictor for its next
+//! value), followed by one frame per sample. Each frame encodes only the delta between
+//! a field's predictor and its real value as a variable-length integer, so a
+//! near-constant power reading costs about a byte per frame instead of a full `f64`.
+
+use hardware_plugins::Measurement;
+use std::io::{self, Read, Write};
+use thiserror::Error;
+
+const MAGIC: &[u8; 4] = b"CGTR";
+const VERSION: u8 = 1;
+
+/// `Measurement::joules` is scaled to this many units-per-joule before being rounded
+/// to an integer, so the delta encoding operates on whole microjoules rather than
+/// floating point.
+const MICROJOULE_SCALE: f64 = 1_000_000.0;
+
+/// How a field's next raw value is predicted from its history, so a frame only needs
+/// to encode the (usually small) error between the predictor and the real value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Predictor {
+    /// Predict the field stays at its previous value; frames encode `current - previous`.
+    Previous,
+    /// Predict zero; frames encode the raw value verbatim. Useful for fields with no
+    /// sample-to-sample locality.
+    Zero,
+}
+
+impl Predictor {
+    fn tag(self) -> u8 {
+        match self {
+            Predictor::Previous => 0,
+            Predictor::Zero => 1,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self, ParseError> {
+        match tag {
+            0 => Ok(Predictor::Previous),
+            1 => Ok(Predictor::Zero),
+            other => Err(ParseError::UnknownPredictor(other)),
+        }
+    }
+}
+
+/// Describes one scalar time series carried by the trace, typically one per
+/// registered hardware plugin.
+#[derive(Debug, Clone)]
+pub struct FieldDef {
+    pub plugin_name: String,
+    pub metric_name: String,
+    pub unit: String,
+    pub predictor: Predictor,
+}
+
+impl FieldDef {
+    /// A `joules`-metric field for a plugin named `plugin_name`, predicted from its
+    /// previous value — the common case for `Measurement::joules` series.
+    pub fn joules(plugin_name: impl Into<String>) -> Self {
+        Self {
+            plugin_name: plugin_name.into(),
+            metric_name: "joules".to_string(),
+            unit: "joules".to_string(),
+            predictor: Predictor::Previous,
+        }
+    }
+}
+
+/// Errors that can occur while parsing a trace.
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("I/O error reading trace: {0}")]
+    Io(#[from] io::Error),
+    #[error("not a codegreen trace file (bad magic bytes)")]
+    BadMagic,
+    #[error("unsupported trace format version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("unknown predictor tag: {0}")]
+    UnknownPredictor(u8),
+    #[error("frame data references field index {0}, which has no header definition")]
+    MissingFieldDefinition(usize),
+    #[error("truncated frame: stream ended mid-frame")]
+    TruncatedFrame,
+}
+
+fn write_uvarint(writer: &mut impl Write, mut value: u64) -> io::Result<()> {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        writer.write_all(&[byte])?;
+        if value == 0 {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn write_zigzag_varint(writer: &mut impl Write, value: i64) -> io::Result<()> {
+    write_uvarint(writer, zigzag_encode(value))
+}
+
+fn write_string(writer: &mut impl Write, value: &str) -> io::Result<()> {
+    write_uvarint(writer, value.len() as u64)?;
+    writer.write_all(value.as_bytes())
+}
+
+/// Reads one required byte, mapping a clean EOF to `TruncatedFrame` rather than
+/// bubbling up a raw `UnexpectedEof` I/O error.
+fn read_byte_required(reader: &mut impl Read) -> Result<u8, ParseError> {
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf).map_err(|e| {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            ParseError::TruncatedFrame
+        } else {
+            ParseError::Io(e)
+        }
+    })?;
+    Ok(buf[0])
+}
+
+fn read_uvarint(reader: &mut impl Read) -> Result<u64, ParseError> {
+    let mut result = 0u64;
+    let mut shift = 0u32;
+    loop {
+        let byte = read_byte_required(reader)?;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(result)
+}
+
+/// Like `read_uvarint`, but a clean EOF on the very first byte (i.e. end of the trace,
+/// not a truncated frame) is reported as `Ok(None)` instead of an error.
+fn try_read_uvarint(reader: &mut impl Read) -> Result<Option<u64>, ParseError> {
+    let mut first = [0u8; 1];
+    let n = reader.read(&mut first).map_err(ParseError::Io)?;
+    if n == 0 {
+        return Ok(None);
+    }
+    if first[0] & 0x80 == 0 {
+        return Ok(Some(first[0] as u64));
+    }
+    let mut result = (first[0] & 0x7f) as u64;
+    let mut shift = 7u32;
+    loop {
+        let byte = read_byte_required(reader)?;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(Some(result))
+}
+
+fn read_string(reader: &mut impl Read) -> Result<String, ParseError> {
+    let len = read_uvarint(reader)? as usize;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf).map_err(|e| {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            ParseError::TruncatedFrame
+        } else {
+            ParseError::Io(e)
+        }
+    })?;
+    String::from_utf8(buf)
+        .map_err(|e| ParseError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+/// Appends delta-encoded frames to a binary energy trace, writing the field header on
+/// construction.
+pub struct TraceWriter<W: Write> {
+    writer: W,
+    fields: Vec<FieldDef>,
+    previous_values: Vec<i64>,
+    previous_timestamp_ms: Option<i64>,
+}
+
+impl<W: Write> TraceWriter<W> {
+    /// Create a new trace, writing the header (magic, version, and `fields`) immediately.
+    pub fn new(mut writer: W, fields: Vec<FieldDef>) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        write_uvarint(&mut writer, fields.len() as u64)?;
+        for field in &fields {
+            write_string(&mut writer, &field.plugin_name)?;
+            write_string(&mut writer, &field.metric_name)?;
+            write_string(&mut writer, &field.unit)?;
+            writer.write_all(&[field.predictor.tag()])?;
+        }
+        let previous_values = vec![0i64; fields.len()];
+        Ok(Self {
+            writer,
+            fields,
+            previous_values,
+            previous_timestamp_ms: None,
+        })
+    }
+
+    /// The fields this trace's frames are indexed against.
+    pub fn fields(&self) -> &[FieldDef] {
+        &self.fields
+    }
+
+    /// Append one frame: `values` is a sparse set of `(field_index, raw_value)` pairs
+    /// (some plugins may not have produced a reading this tick), delta-encoded against
+    /// each field's predictor.
+    pub fn write_frame(&mut self, timestamp_ms: i64, values: &[(usize, f64)]) -> io::Result<()> {
+        let ts_field = match self.previous_timestamp_ms {
+            None => timestamp_ms,
+            Some(prev) => timestamp_ms - prev,
+        };
+        write_zigzag_varint(&mut self.writer, ts_field)?;
+        self.previous_timestamp_ms = Some(timestamp_ms);
+
+        write_uvarint(&mut self.writer, values.len() as u64)?;
+        for &(field_index, raw_value) in values {
+            let scaled = (raw_value * MICROJOULE_SCALE).round() as i64;
+            let encoded = match self.fields[field_index].predictor {
+                Predictor::Previous => {
+                    let delta = scaled - self.previous_values[field_index];
+                    self.previous_values[field_index] = scaled;
+                    delta
+                }
+                Predictor::Zero => scaled,
+            };
+            write_uvarint(&mut self.writer, field_index as u64)?;
+            write_zigzag_varint(&mut self.writer, encoded)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the underlying writer and return it.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Parses a binary energy trace back into `Measurement`s, applying each field's
+/// predictor in reverse to reconstruct absolute values.
+pub struct TraceReader<R: Read> {
+    reader: R,
+    fields: Vec<FieldDef>,
+}
+
+impl<R: Read> TraceReader<R> {
+    /// Parse the header and return a reader positioned at the first frame.
+    pub fn new(mut reader: R) -> Result<Self, ParseError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic).map_err(|e| {
+            if e.kind() == io::ErrorKind::UnexpectedEof {
+                ParseError::BadMagic
+            } else {
+                ParseError::Io(e)
+            }
+        })?;
+        if &magic != MAGIC {
+            return Err(ParseError::BadMagic);
+        }
+
+        let version = read_byte_required(&mut reader)?;
+        if version != VERSION {
+            return Err(ParseError::UnsupportedVersion(version));
+        }
+
+        let field_count = read_uvarint(&mut reader)? as usize;
+        let mut fields = Vec::with_capacity(field_count);
+        for _ in 0..field_count {
+            let plugin_name = read_string(&mut reader)?;
+            let metric_name = read_string(&mut reader)?;
+            let unit = read_string(&mut reader)?;
+            let predictor = Predictor::from_tag(read_byte_required(&mut reader)?)?;
+            fields.push(FieldDef {
+                plugin_name,
+                metric_name,
+                unit,
+                predictor,
+            });
+        }
+
+        Ok(Self { reader, fields })
+    }
+
+    /// The fields declared in this trace's header.
+    pub fn fields(&self) -> &[FieldDef] {
+        &self.fields
+    }
+
+    /// Parse every remaining frame, reconstructing one `Measurement` per `(frame,
+    /// field)` pair present in the trace, in chronological order.
+    pub fn read_all(mut self) -> Result<Vec<Measurement>, ParseError> {
+        let mut previous_values = vec![0i64; self.fields.len()];
+        let mut previous_timestamp_ms: Option<i64> = None;
+        let mut measurements = Vec::new();
+
+        loop {
+            let ts_raw = match try_read_uvarint(&mut self.reader)? {
+                Some(v) => v,
+                None => break,
+            };
+            let ts_field = zigzag_decode(ts_raw);
+            let timestamp_ms = match previous_timestamp_ms {
+                None => ts_field,
+                Some(prev) => prev + ts_field,
+            };
+            previous_timestamp_ms = Some(timestamp_ms);
+
+            let value_count = read_uvarint(&mut self.reader)? as usize;
+            for _ in 0..value_count {
+                let field_index = read_uvarint(&mut self.reader)? as usize;
+                let field = self
+                    .fields
+                    .get(field_index)
+                    .ok_or(ParseError::MissingFieldDefinition(field_index))?;
+                let encoded = zigzag_decode(read_uvarint(&mut self.reader)?);
+
+                let scaled = match field.predictor {
+                    Predictor::Previous => {
+                        let value = previous_values[field_index] + encoded;
+                        previous_values[field_index] = value;
+                        value
+                    }
+                    Predictor::Zero => encoded,
+                };
+
+                let timestamp = chrono::DateTime::<chrono::Utc>::from_timestamp_millis(timestamp_ms)
+                    .unwrap_or_else(chrono::Utc::now);
+
+                measurements.push(Measurement {
+                    timestamp,
+                    joules: scaled as f64 / MICROJOULE_SCALE,
+                    source: field.plugin_name.clone(),
+                    max_joules: None,
+                });
+            }
+        }
+
+        Ok(measurements)
+    }
+}
@@ -1,12 +1,22 @@
 //! AMD-specific hardware plugins
 
 use crate::HardwareError;
-// use crate::EnergyMeasurement;
+use crate::HardwarePluginError;
 use std::time::Instant;
-use crate::{Measurement};
-use crate::common::{BasePlugin, DefaultPluginImpl, HardwarePlugin};
+use crate::{Measurement, ThermalReading};
+use crate::common::{BasePlugin, DefaultPluginImpl, HardwarePlugin, ThermalPlugin};
 use chrono;
 use async_trait::async_trait;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Root of the Linux powercap sysfs tree exposing AMD per-socket energy counters.
+const AMD_ENERGY_ROOT: &str = "/sys/class/powercap/amd-energy";
+
+/// Root of the Linux DRM sysfs tree; AMD GPU power/temperature live under each card's
+/// `device/hwmon/hwmonN` subdirectory.
+const AMD_GPU_DRM_ROOT: &str = "/sys/class/drm";
 
 /// AMD Energy Monitoring plugin
 pub struct AmdEnergyPlugin {
@@ -20,15 +30,36 @@ impl AmdEnergyPlugin {
             base: BasePlugin::new(
                 "amd-energy",
                 "AMD energy monitoring plugin",
-                "/sys/class/powercap/amd-energy".to_string(),
+                AMD_ENERGY_ROOT.to_string(),
             ),
         })
     }
 
     /// Read energy measurements from AMD Energy Monitoring
-    pub fn read_measurements(&self) -> Result<Vec<Measurement>, HardwareError> {
-        // TODO: Implement AMD energy measurements
-        Ok(Vec::new())
+    ///
+    /// Mirrors `IntelRaplPlugin::read_measurements`: enumerates every per-socket domain
+    /// directory under `/sys/class/powercap/amd-energy` (`amd-energy:0`, `amd-energy:1`,
+    /// ...) and emits one `Measurement` per socket, labeled with the domain's `name` file.
+    pub fn read_measurements(&self) -> Result<Vec<Measurement>, HardwarePluginError> {
+        let root = Path::new(self.base.device_path());
+        if !root.exists() {
+            return Err(HardwarePluginError::UnsupportedHardware(
+                "AMD energy powercap tree not found".to_string(),
+            ));
+        }
+
+        let mut measurements = Vec::new();
+        for socket_dir in amd_energy_domain_dirs(root)? {
+            measurements.push(read_socket_measurement(&socket_dir)?);
+        }
+
+        if measurements.is_empty() {
+            return Err(HardwarePluginError::UnsupportedHardware(
+                "No readable energy counters found under amd-energy".to_string(),
+            ));
+        }
+
+        Ok(measurements)
     }
 }
 
@@ -42,8 +73,290 @@ impl DefaultPluginImpl for AmdEnergyPlugin {
     }
 }
 
+/// Enumerate per-socket domain directories (`amd-energy:N`).
+fn amd_energy_domain_dirs(root: &Path) -> Result<Vec<PathBuf>, HardwarePluginError> {
+    let entries = fs::read_dir(root).map_err(|e| {
+        HardwarePluginError::MeasurementError(format!("Failed to read {}: {}", root.display(), e))
+    })?;
+
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with("amd-energy:"))
+                .unwrap_or(false)
+        })
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Read a single socket domain's `name`, `energy_uj` counter, and `max_energy_range_uj`
+/// and turn it into a `Measurement`. `max_energy_range_uj` is carried along as
+/// `max_joules` so a session spanning a wraparound of this fixed-width counter can
+/// compute a correct delta (see `core::adapters::BaseAdapter::calculate_energy_delta`)
+/// instead of a large negative number.
+fn read_socket_measurement(socket_dir: &Path) -> Result<Measurement, HardwarePluginError> {
+    let name = read_sysfs_string(&socket_dir.join("name"))?;
+    let energy_uj = read_sysfs_u64(&socket_dir.join("energy_uj"))?;
+    let max_energy_range_uj = read_sysfs_u64(&socket_dir.join("max_energy_range_uj")).ok();
+
+    Ok(Measurement {
+        timestamp: chrono::Utc::now(),
+        joules: energy_uj as f64 / 1_000_000.0,
+        source: name,
+        max_joules: max_energy_range_uj.map(|max_uj| max_uj as f64 / 1_000_000.0),
+    })
+}
+
 /// Check if AMD Energy Monitoring is available on the system
+///
+/// Returns true only if the powercap root exists and at least one socket's `energy_uj`
+/// counter is actually readable.
 pub fn is_amd_energy_available() -> bool {
-    // TODO: Implement AMD energy availability check
-    false
-} 
\ No newline at end of file
+    let root = Path::new(AMD_ENERGY_ROOT);
+    if !root.exists() {
+        return false;
+    }
+
+    match amd_energy_domain_dirs(root) {
+        Ok(dirs) => dirs
+            .iter()
+            .any(|dir| fs::read_to_string(dir.join("energy_uj")).is_ok()),
+        Err(_) => false,
+    }
+}
+
+fn read_sysfs_string(path: &Path) -> Result<String, HardwarePluginError> {
+    fs::read_to_string(path)
+        .map(|s| s.trim().to_string())
+        .map_err(|e| sysfs_read_error(path, e))
+}
+
+fn read_sysfs_u64(path: &Path) -> Result<u64, HardwarePluginError> {
+    let raw = read_sysfs_string(path)?;
+    raw.parse::<u64>().map_err(|e| {
+        HardwarePluginError::MeasurementError(format!(
+            "Failed to parse {} as u64: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn sysfs_read_error(path: &Path, err: std::io::Error) -> HardwarePluginError {
+    if err.kind() == std::io::ErrorKind::PermissionDenied {
+        HardwarePluginError::PermissionDenied(format!(
+            "Permission denied reading {} (AMD energy counters require root or \
+             CAP_DAC_READ_SEARCH on newer kernels)",
+            path.display()
+        ))
+    } else {
+        HardwarePluginError::MeasurementError(format!("Failed to read {}: {}", path.display(), err))
+    }
+}
+
+/// Running state for `AmdGpuPlugin`'s power integration, mirroring
+/// `EstimationPlugin`'s accumulate-since-last-sample approach but driven by a real
+/// `power1_average` reading instead of a CPU-utilization-scaled TDP.
+struct AmdGpuState {
+    accumulated_joules: f64,
+    last_sample: Instant,
+}
+
+/// AMD GPU power and temperature monitoring plugin, reading the `hwmon` interface each
+/// `amdgpu` DRM device exposes, for parity with `NvidiaGpuPlugin`.
+pub struct AmdGpuPlugin {
+    base: BasePlugin,
+    state: Mutex<AmdGpuState>,
+}
+
+impl AmdGpuPlugin {
+    /// Create a new AMD GPU plugin
+    pub fn new() -> Result<Self, HardwareError> {
+        Ok(Self {
+            base: BasePlugin::new(
+                "amd-gpu",
+                "AMD GPU power and temperature monitoring plugin",
+                AMD_GPU_DRM_ROOT.to_string(),
+            ),
+            state: Mutex::new(AmdGpuState {
+                accumulated_joules: 0.0,
+                last_sample: Instant::now(),
+            }),
+        })
+    }
+
+    /// Find every `.../device/hwmon/hwmonN` directory under an AMD `/sys/class/drm/cardN`
+    /// entry. There's one per GPU, so a host with no `amdgpu`-bound card returns empty.
+    fn hwmon_dirs() -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        let card_entries = match fs::read_dir(AMD_GPU_DRM_ROOT) {
+            Ok(entries) => entries,
+            Err(_) => return dirs,
+        };
+
+        for card_entry in card_entries.filter_map(|e| e.ok()) {
+            let card_path = card_entry.path();
+            let is_bare_card = card_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with("card") && !name.contains('-'))
+                .unwrap_or(false);
+            if !is_bare_card {
+                continue;
+            }
+
+            let hwmon_root = card_path.join("device/hwmon");
+            let hwmon_entries = match fs::read_dir(&hwmon_root) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for hwmon_entry in hwmon_entries.filter_map(|e| e.ok()) {
+                let hwmon_path = hwmon_entry.path();
+                let is_hwmon = hwmon_path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| name.starts_with("hwmon"))
+                    .unwrap_or(false);
+                if is_hwmon {
+                    dirs.push(hwmon_path);
+                }
+            }
+        }
+        dirs.sort();
+        dirs
+    }
+
+    /// Read `power1_average` (microwatts) from every discovered hwmon directory and fold
+    /// the energy accumulated since the last sample into the running total, the same
+    /// integration `EstimationPlugin::sample` does for its modeled power figure.
+    fn sample_joules(&self) -> Result<f64, HardwarePluginError> {
+        let dirs = Self::hwmon_dirs();
+        if dirs.is_empty() {
+            return Err(HardwarePluginError::UnsupportedHardware(
+                "No AMD GPU hwmon power interface found under /sys/class/drm".to_string(),
+            ));
+        }
+
+        let mut total_watts = 0.0;
+        for dir in &dirs {
+            let microwatts = read_sysfs_u64(&dir.join("power1_average"))?;
+            total_watts += microwatts as f64 / 1_000_000.0;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed_seconds = now.duration_since(state.last_sample).as_secs_f64();
+        state.last_sample = now;
+        state.accumulated_joules += total_watts * elapsed_seconds;
+        Ok(state.accumulated_joules)
+    }
+
+    /// Read `temp1_input` (millicelsius) from every discovered hwmon directory and
+    /// average them into a single reading.
+    fn read_temperature(&self) -> Result<f64, HardwarePluginError> {
+        let dirs = Self::hwmon_dirs();
+        if dirs.is_empty() {
+            return Err(HardwarePluginError::UnsupportedHardware(
+                "No AMD GPU hwmon temperature interface found under /sys/class/drm".to_string(),
+            ));
+        }
+
+        let mut total_millicelsius = 0u64;
+        for dir in &dirs {
+            total_millicelsius += read_sysfs_u64(&dir.join("temp1_input"))?;
+        }
+        Ok((total_millicelsius as f64 / dirs.len() as f64) / 1000.0)
+    }
+}
+
+#[async_trait]
+impl HardwarePlugin for AmdGpuPlugin {
+    fn name(&self) -> &'static str {
+        self.base.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.base.description()
+    }
+
+    fn is_available(&self) -> bool {
+        self.base.is_enabled()
+    }
+
+    fn is_supported(&self) -> bool {
+        is_amd_gpu_available()
+    }
+
+    fn initialize(&mut self) -> Result<(), HardwareError> {
+        Ok(())
+    }
+
+    async fn start_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.get_measurement()
+    }
+
+    async fn stop_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.get_measurement()
+    }
+
+    fn get_measurement(&self) -> Result<Measurement, HardwareError> {
+        let joules = self
+            .sample_joules()
+            .map_err(|e| HardwareError::SensorError(e.to_string()))?;
+        Ok(Measurement {
+            timestamp: chrono::Utc::now(),
+            joules,
+            source: self.base.name().to_string(),
+            max_joules: None,
+        })
+    }
+
+    fn supported_metrics(&self) -> Vec<&'static str> {
+        vec!["power", "energy", "temperature"]
+    }
+
+    async fn get_total_energy_consumption(&self) -> Result<f64, HardwareError> {
+        self.sample_joules()
+            .map_err(|e| HardwareError::SensorError(e.to_string()))
+    }
+}
+
+impl ThermalPlugin for AmdGpuPlugin {
+    fn name(&self) -> &'static str {
+        self.base.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.base.description()
+    }
+
+    fn is_available(&self) -> bool {
+        self.base.is_enabled()
+    }
+
+    fn is_supported(&self) -> bool {
+        is_amd_gpu_available()
+    }
+
+    fn get_reading(&self) -> Result<ThermalReading, HardwareError> {
+        let celsius = self
+            .read_temperature()
+            .map_err(|e| HardwareError::SensorError(e.to_string()))?;
+        Ok(ThermalReading {
+            timestamp: chrono::Utc::now(),
+            celsius,
+            sensor: self.base.name().to_string(),
+        })
+    }
+}
+
+/// Check if an AMD GPU's hwmon power interface is available on the system.
+pub fn is_amd_gpu_available() -> bool {
+    AmdGpuPlugin::hwmon_dirs()
+        .iter()
+        .any(|dir| fs::read_to_string(dir.join("power1_average")).is_ok())
+}
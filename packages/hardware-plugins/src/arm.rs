@@ -3,8 +3,8 @@
 use crate::HardwarePluginError;
 // use crate::EnergyMeasurement;
 use std::time::Instant;
-use crate::{Measurement, HardwareError};
-use crate::common::{BasePlugin, DefaultPluginImpl, HardwarePlugin};
+use crate::{Measurement, HardwareError, ThermalReading};
+use crate::common::{BasePlugin, DefaultPluginImpl, DefaultThermalPluginImpl, HardwarePlugin, ThermalPlugin};
 use chrono;
 use async_trait::async_trait;
 
@@ -46,4 +46,51 @@ impl DefaultPluginImpl for ArmEnergyPlugin {
 pub fn is_arm_energy_available() -> bool {
     // TODO: Implement ARM energy availability check
     false
+}
+
+/// ARM thermal sensor plugin, parallel to `ArmEnergyPlugin`.
+///
+/// Not yet implemented: ARM SoCs expose temperature through vendor-specific paths
+/// (e.g. `/sys/class/thermal` zones labeled differently per board, or a vendor hwmon
+/// driver) rather than one standard location the way `/sys/class/powercap` is for
+/// Intel RAPL, so this is a stub until a target board is chosen.
+pub struct ArmThermalPlugin {
+    base: BasePlugin,
+}
+
+impl ArmThermalPlugin {
+    /// Create a new ARM thermal plugin
+    pub fn new() -> Result<Self, HardwareError> {
+        Ok(Self {
+            base: BasePlugin::new(
+                "arm-thermal",
+                "ARM temperature sensor plugin",
+                "/sys/class/thermal/arm".to_string(),
+            ),
+        })
+    }
+
+    /// Read the current ARM temperature reading
+    pub fn read_reading(&self) -> Result<ThermalReading, HardwarePluginError> {
+        // TODO: Implement ARM thermal reading
+        Err(HardwarePluginError::UnsupportedHardware(
+            "ARM thermal sensor support is not yet implemented".to_string(),
+        ))
+    }
+}
+
+impl DefaultThermalPluginImpl for ArmThermalPlugin {
+    fn base(&self) -> &BasePlugin {
+        &self.base
+    }
+
+    fn is_supported(&self) -> bool {
+        is_arm_thermal_available()
+    }
+}
+
+/// Check if ARM thermal sensing is available on the system
+pub fn is_arm_thermal_available() -> bool {
+    // TODO: Implement ARM thermal availability check
+    false
 } 
\ No newline at end of file
@@ -3,7 +3,7 @@
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use chrono::Utc;
-use crate::{Measurement, HardwareError};
+use crate::{Measurement, HardwareError, ThermalReading};
 
 /// Common configuration for all hardware plugins
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,6 +126,8 @@ impl<T: DefaultPluginImpl> HardwarePlugin for T {
         Ok(Measurement {
             timestamp: Utc::now(),
             joules: 0.0,
+            source: self.base().name().to_string(),
+            max_joules: None,
         })
     }
 
@@ -133,6 +135,8 @@ impl<T: DefaultPluginImpl> HardwarePlugin for T {
         Ok(Measurement {
             timestamp: Utc::now(),
             joules: 0.0,
+            source: self.base().name().to_string(),
+            max_joules: None,
         })
     }
 
@@ -140,6 +144,8 @@ impl<T: DefaultPluginImpl> HardwarePlugin for T {
         Ok(Measurement {
             timestamp: Utc::now(),
             joules: 0.0,
+            source: self.base().name().to_string(),
+            max_joules: None,
         })
     }
 
@@ -150,4 +156,58 @@ impl<T: DefaultPluginImpl> HardwarePlugin for T {
     async fn get_total_energy_consumption(&self) -> Result<f64, HardwareError> {
         Ok(0.0)
     }
+}
+
+/// Trait for temperature sensor plugins, parallel to `HardwarePlugin` but for
+/// `ThermalReading`s instead of energy `Measurement`s. Kept separate rather than
+/// folded into `HardwarePlugin` since a source can have one without the other
+/// (e.g. a battery-discharge energy source has no associated sensor).
+pub trait ThermalPlugin: Send + Sync {
+    /// Get the name of the thermal plugin
+    fn name(&self) -> &'static str;
+
+    /// Get a description of the thermal plugin
+    fn description(&self) -> &'static str;
+
+    /// Check if the sensor is available and enabled
+    fn is_available(&self) -> bool;
+
+    /// Check if the sensor is supported on this host
+    fn is_supported(&self) -> bool;
+
+    /// Get the current temperature reading
+    fn get_reading(&self) -> Result<ThermalReading, HardwareError>;
+}
+
+/// Default implementations for common thermal plugin functionality
+pub trait DefaultThermalPluginImpl: ThermalPlugin {
+    fn base(&self) -> &BasePlugin;
+    fn is_supported(&self) -> bool;
+}
+
+/// Default implementations for ThermalPlugin trait
+impl<T: DefaultThermalPluginImpl> ThermalPlugin for T {
+    fn name(&self) -> &'static str {
+        self.base().name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.base().description()
+    }
+
+    fn is_available(&self) -> bool {
+        self.base().is_enabled()
+    }
+
+    fn is_supported(&self) -> bool {
+        DefaultThermalPluginImpl::is_supported(self)
+    }
+
+    fn get_reading(&self) -> Result<ThermalReading, HardwareError> {
+        Ok(ThermalReading {
+            timestamp: Utc::now(),
+            celsius: 0.0,
+            sensor: self.base().name().to_string(),
+        })
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,139 @@
+//! Portable CPU-utilization energy estimation plugin
+//!
+//! `IntelRaplPlugin`/`AmdEnergyPlugin`/`ArmEnergyPlugin`/`NvidiaGpuPlugin` all depend on
+//! a hardware counter that doesn't exist on every host (a non-Intel laptop, a VM with no
+//! RAPL passthrough, ...). `EstimationPlugin` is the fallback of last resort: it samples
+//! per-core CPU utilization via `sysinfo` and scales a configurable TDP by that
+//! utilization to synthesize an energy reading. `PluginRegistry::new` registers it
+//! automatically whenever none of the hardware-counter plugins report `is_supported()`.
+
+use crate::common::{BasePlugin, HardwarePlugin};
+use crate::{HardwareError, Measurement};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Mutex;
+use std::time::Instant;
+use sysinfo::{CpuExt, System, SystemExt};
+
+/// Source label (and plugin name) used on `Measurement`s produced by this plugin, so
+/// consumers can tell a modeled reading apart from one off a real hardware counter.
+pub const ESTIMATED_SOURCE: &str = "estimated";
+
+/// Assumed thermal design power, in watts, used when no explicit TDP is given to
+/// `EstimationPlugin::with_tdp`. Roughly that of a modern laptop/desktop CPU package;
+/// the whole point of this plugin is a rough order-of-magnitude estimate, not a
+/// substitute for a real hardware counter.
+const DEFAULT_TDP_WATTS: f64 = 65.0;
+
+struct EstimationState {
+    system: System,
+    accumulated_joules: f64,
+    last_sample: Instant,
+}
+
+/// Fallback energy plugin for hosts with no RAPL/PMU/GPU energy counter: synthesizes a
+/// `Measurement` by integrating `tdp_watts * average CPU utilization` over time, the
+/// same way a real hardware counter accumulates joules, so callers that window a
+/// measurement via `start_measurement`/`stop_measurement` can take a delta the usual way.
+pub struct EstimationPlugin {
+    base: BasePlugin,
+    tdp_watts: f64,
+    state: Mutex<EstimationState>,
+}
+
+impl EstimationPlugin {
+    /// Create a new estimation plugin assuming `DEFAULT_TDP_WATTS`.
+    pub fn new() -> Result<Self, HardwareError> {
+        Self::with_tdp(DEFAULT_TDP_WATTS)
+    }
+
+    /// Create a new estimation plugin against a caller-supplied TDP, for hosts whose
+    /// actual package power limit is known (e.g. read from a spec sheet or BIOS).
+    pub fn with_tdp(tdp_watts: f64) -> Result<Self, HardwareError> {
+        Ok(Self {
+            base: BasePlugin::new(
+                ESTIMATED_SOURCE,
+                "Portable CPU-utilization energy estimation plugin",
+                String::new(),
+            ),
+            tdp_watts,
+            state: Mutex::new(EstimationState {
+                system: System::new(),
+                accumulated_joules: 0.0,
+                last_sample: Instant::now(),
+            }),
+        })
+    }
+
+    /// Sample CPU utilization, estimate instantaneous power draw, and fold the energy
+    /// accumulated since the last sample into the running total. Returns the new total.
+    fn sample(&self) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        state.system.refresh_cpu();
+
+        let cpus = state.system.cpus();
+        let utilization = if cpus.is_empty() {
+            0.0
+        } else {
+            let total: f32 = cpus.iter().map(|cpu| cpu.cpu_usage()).sum();
+            (total / cpus.len() as f32) as f64 / 100.0
+        };
+        let watts = self.tdp_watts * utilization;
+
+        let now = Instant::now();
+        let elapsed_seconds = now.duration_since(state.last_sample).as_secs_f64();
+        state.last_sample = now;
+        state.accumulated_joules += watts * elapsed_seconds;
+
+        state.accumulated_joules
+    }
+}
+
+#[async_trait]
+impl HardwarePlugin for EstimationPlugin {
+    fn name(&self) -> &'static str {
+        self.base.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.base.description()
+    }
+
+    fn is_available(&self) -> bool {
+        self.base.is_enabled()
+    }
+
+    fn is_supported(&self) -> bool {
+        // Always usable as a last resort: CPU utilization can be sampled on any host.
+        true
+    }
+
+    fn initialize(&mut self) -> Result<(), HardwareError> {
+        Ok(())
+    }
+
+    async fn start_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.get_measurement()
+    }
+
+    async fn stop_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.get_measurement()
+    }
+
+    fn get_measurement(&self) -> Result<Measurement, HardwareError> {
+        Ok(Measurement {
+            timestamp: Utc::now(),
+            joules: self.sample(),
+            source: ESTIMATED_SOURCE.to_string(),
+            max_joules: None,
+        })
+    }
+
+    fn supported_metrics(&self) -> Vec<&'static str> {
+        vec!["power", "energy"]
+    }
+
+    async fn get_total_energy_consumption(&self) -> Result<f64, HardwareError> {
+        Ok(self.sample())
+    }
+}
@@ -0,0 +1,196 @@
+//! Host-level resource-utilization sampling, paired with energy `Measurement`s so a
+//! session can express energy-per-CPU-second or energy-per-byte instead of treating
+//! every joule as attributable to one workload.
+//!
+//! Backed by `sysinfo`, the same crate `EstimationPlugin` already uses for CPU
+//! utilization, so collection degrades across platforms the same way: `sysinfo` itself
+//! absorbs the per-OS differences (Linux `/proc`, etc.) internally, so unlike the
+//! sysfs-based RAPL plugin, `is_supported()` doesn't need its own platform probe.
+
+use crate::common::BasePlugin;
+use crate::{HardwareError, HardwarePlugin, Measurement};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sysinfo::{CpuExt, DiskExt, NetworkExt, System, SystemExt};
+
+/// Name (and placeholder `Measurement` source) this plugin registers under, so it's
+/// discoverable via `PluginRegistry::get_plugin_by_name` like any energy plugin.
+pub const HOST_METRICS_SOURCE: &str = "host_metrics";
+
+/// Default interval between real `sysinfo` refreshes. A `get_host_metrics` call inside
+/// this window of the last one returns the cached snapshot instead of re-querying the
+/// OS, since CPU/memory/network counters don't need to be read faster than a session
+/// samples them.
+const DEFAULT_COLLECTION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A snapshot of system-wide resource utilization, sampled alongside an energy
+/// `Measurement` so a report can normalize joules against what else the machine was
+/// doing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostMetrics {
+    pub timestamp: DateTime<Utc>,
+    /// Utilization percentage (0-100) of each logical CPU core.
+    pub cpu_utilization_percent: Vec<f64>,
+    /// 1/5/15-minute load averages, as reported by the OS.
+    pub load_average: (f64, f64, f64),
+    pub memory_used_bytes: u64,
+    pub memory_total_bytes: u64,
+    /// Cumulative received/transmitted bytes per network interface, keyed by
+    /// interface name (e.g. `"eth0"`).
+    pub network_bytes: HashMap<String, (u64, u64)>,
+    /// Available/total bytes per disk, keyed by device name. `sysinfo` doesn't expose
+    /// cumulative read/write byte counters portably, so space rather than throughput is
+    /// tracked here — enough to spot a disk-bound workload filling up storage.
+    pub disk_space_bytes: HashMap<String, (u64, u64)>,
+}
+
+struct HostMetricsState {
+    system: System,
+    last_sample: Option<(Instant, HostMetrics)>,
+}
+
+/// Samples CPU utilization per core, load average, memory usage, per-interface
+/// network byte counters, and per-disk space on a configurable collection interval. Implements
+/// `HardwarePlugin` so it's discoverable through `PluginRegistry` the same way an
+/// energy source is, though `get_measurement` only ever reports a zero-joule
+/// placeholder (host metrics aren't energy) — `get_host_metrics` is the real entry
+/// point for its data.
+pub struct HostMetricsPlugin {
+    base: BasePlugin,
+    collection_interval: Duration,
+    state: Mutex<HostMetricsState>,
+}
+
+impl HostMetricsPlugin {
+    /// Create a new plugin sampling at most once per `DEFAULT_COLLECTION_INTERVAL`.
+    pub fn new() -> Self {
+        Self::with_collection_interval(DEFAULT_COLLECTION_INTERVAL)
+    }
+
+    /// Create a new plugin sampling at most once per `collection_interval`.
+    pub fn with_collection_interval(collection_interval: Duration) -> Self {
+        Self {
+            base: BasePlugin::new(HOST_METRICS_SOURCE, "Host resource-utilization sampler", String::new()),
+            collection_interval,
+            state: Mutex::new(HostMetricsState {
+                system: System::new_all(),
+                last_sample: None,
+            }),
+        }
+    }
+
+    /// Sample (or, within `collection_interval` of the last sample, return the cached)
+    /// host resource-utilization snapshot.
+    pub fn get_host_metrics(&self) -> Result<HostMetrics, HardwareError> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some((sampled_at, metrics)) = &state.last_sample {
+            if sampled_at.elapsed() < self.collection_interval {
+                return Ok(metrics.clone());
+            }
+        }
+
+        state.system.refresh_cpu();
+        state.system.refresh_memory();
+        state.system.refresh_networks();
+        state.system.refresh_disks();
+
+        let cpu_utilization_percent = state
+            .system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.cpu_usage() as f64)
+            .collect();
+
+        let load = state.system.load_average();
+
+        let network_bytes = state
+            .system
+            .networks()
+            .iter()
+            .map(|(name, data)| (name.clone(), (data.total_received(), data.total_transmitted())))
+            .collect();
+
+        let disk_space_bytes = state
+            .system
+            .disks()
+            .iter()
+            .enumerate()
+            .map(|(i, disk)| {
+                let name = disk.name().to_string_lossy().into_owned();
+                let name = if name.is_empty() { format!("disk{}", i) } else { name };
+                (name, (disk.available_space(), disk.total_space()))
+            })
+            .collect();
+
+        let metrics = HostMetrics {
+            timestamp: Utc::now(),
+            cpu_utilization_percent,
+            load_average: (load.one, load.five, load.fifteen),
+            memory_used_bytes: state.system.used_memory(),
+            memory_total_bytes: state.system.total_memory(),
+            network_bytes,
+            disk_space_bytes,
+        };
+
+        state.last_sample = Some((Instant::now(), metrics.clone()));
+        Ok(metrics)
+    }
+}
+
+#[async_trait]
+impl HardwarePlugin for HostMetricsPlugin {
+    fn name(&self) -> &'static str {
+        self.base.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.base.description()
+    }
+
+    fn is_available(&self) -> bool {
+        self.base.is_enabled()
+    }
+
+    fn is_supported(&self) -> bool {
+        // `sysinfo` itself degrades gracefully across platforms, so there's no
+        // hardware precondition to probe the way RAPL checks for `/sys/class/powercap`.
+        true
+    }
+
+    fn initialize(&mut self) -> Result<(), HardwareError> {
+        Ok(())
+    }
+
+    async fn start_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.get_measurement()
+    }
+
+    async fn stop_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.get_measurement()
+    }
+
+    fn get_measurement(&self) -> Result<Measurement, HardwareError> {
+        // Host metrics aren't energy; this placeholder only exists so the plugin shows
+        // up through the same `PluginRegistry`/`HardwarePlugin` surface every energy
+        // source does. Use `get_host_metrics` for the real payload.
+        Ok(Measurement {
+            timestamp: Utc::now(),
+            joules: 0.0,
+            source: HOST_METRICS_SOURCE.to_string(),
+            max_joules: None,
+        })
+    }
+
+    fn supported_metrics(&self) -> Vec<&'static str> {
+        vec!["cpu_utilization", "load_average", "memory", "network", "disk"]
+    }
+
+    async fn get_total_energy_consumption(&self) -> Result<f64, HardwareError> {
+        Ok(0.0)
+    }
+}
@@ -7,6 +7,11 @@ use crate::{Measurement, HardwareError};
 use crate::common::{BasePlugin, DefaultPluginImpl, HardwarePlugin};
 use chrono;
 use async_trait::async_trait;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Root of the Linux powercap sysfs tree exposing Intel RAPL domains.
+const RAPL_ROOT: &str = "/sys/class/powercap/intel-rapl";
 
 /// Intel RAPL (Running Average Power Limit) plugin
 pub struct IntelRaplPlugin {
@@ -20,15 +25,37 @@ impl IntelRaplPlugin {
             base: BasePlugin::new(
                 "intel-rapl",
                 "Intel RAPL energy monitoring plugin",
-                "/sys/class/powercap/intel-rapl".to_string(),
+                RAPL_ROOT.to_string(),
             ),
         })
     }
 
     /// Read energy measurements from Intel RAPL
+    ///
+    /// Enumerates every domain directory under `/sys/class/powercap/intel-rapl`
+    /// (`intel-rapl:0`, `intel-rapl:0:0`, ...) and emits one `Measurement` per
+    /// domain, labeled with the domain's `name` file (`package-0`, `core`,
+    /// `uncore`, `dram`, ...).
     pub fn read_measurements(&self) -> Result<Vec<Measurement>, HardwarePluginError> {
-        // TODO: Implement RAPL measurements
-        Ok(Vec::new())
+        let root = Path::new(self.base.device_path());
+        if !root.exists() {
+            return Err(HardwarePluginError::UnsupportedHardware(
+                "Intel RAPL powercap tree not found".to_string(),
+            ));
+        }
+
+        let mut measurements = Vec::new();
+        for domain_dir in rapl_domain_dirs(root)? {
+            measurements.push(read_domain_measurement(&domain_dir)?);
+        }
+
+        if measurements.is_empty() {
+            return Err(HardwarePluginError::UnsupportedHardware(
+                "No readable RAPL domains found under intel-rapl".to_string(),
+            ));
+        }
+
+        Ok(measurements)
     }
 }
 
@@ -42,8 +69,137 @@ impl DefaultPluginImpl for IntelRaplPlugin {
     }
 }
 
-/// Check if Intel RAPL is available on the system
+/// Enumerate RAPL domain directories (`intel-rapl:N`, `intel-rapl:N:M`, ...).
+fn rapl_domain_dirs(root: &Path) -> Result<Vec<PathBuf>, HardwarePluginError> {
+    let entries = fs::read_dir(root).map_err(|e| {
+        HardwarePluginError::MeasurementError(format!("Failed to read {}: {}", root.display(), e))
+    })?;
+
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with("intel-rapl:"))
+                .unwrap_or(false)
+        })
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Read a single domain's `name`, `energy_uj` counter, and `max_energy_range_uj` and
+/// turn it into a `Measurement`. `max_energy_range_uj` is what the counter wraps back
+/// to zero at; it's carried along as `max_joules` so a session spanning a wraparound
+/// can compute a correct delta (see `rapl_energy_delta_uj` and
+/// `core::adapters::BaseAdapter::calculate_energy_delta`) instead of a large negative
+/// number.
+fn read_domain_measurement(domain_dir: &Path) -> Result<Measurement, HardwarePluginError> {
+    let name = read_sysfs_string(&domain_dir.join("name"))?;
+    let energy_uj = read_sysfs_u64(&domain_dir.join("energy_uj"))?;
+    let max_energy_range_uj = read_sysfs_u64(&domain_dir.join("max_energy_range_uj")).ok();
+
+    Ok(Measurement {
+        timestamp: chrono::Utc::now(),
+        joules: energy_uj as f64 / 1_000_000.0,
+        source: name,
+        max_joules: max_energy_range_uj.map(|max_uj| max_uj as f64 / 1_000_000.0),
+    })
+}
+
+/// Compute the wraparound-aware energy delta (in raw microjoules) between two
+/// readings of the same monotonic `energy_uj` counter, given the domain's
+/// `max_energy_range_uj`.
+pub fn rapl_energy_delta_uj(start_uj: u64, end_uj: u64, max_energy_range_uj: u64) -> u64 {
+    if end_uj >= start_uj {
+        end_uj - start_uj
+    } else {
+        (max_energy_range_uj - start_uj) + end_uj
+    }
+}
+
+fn read_sysfs_string(path: &Path) -> Result<String, HardwarePluginError> {
+    fs::read_to_string(path)
+        .map(|s| s.trim().to_string())
+        .map_err(|e| sysfs_read_error(path, e))
+}
+
+fn read_sysfs_u64(path: &Path) -> Result<u64, HardwarePluginError> {
+    let raw = read_sysfs_string(path)?;
+    raw.parse::<u64>().map_err(|e| {
+        HardwarePluginError::MeasurementError(format!(
+            "Failed to parse {} as u64: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn sysfs_read_error(path: &Path, err: std::io::Error) -> HardwarePluginError {
+    if err.kind() == std::io::ErrorKind::PermissionDenied {
+        HardwarePluginError::PermissionDenied(format!(
+            "Permission denied reading {} (RAPL energy counters require root or \
+             CAP_DAC_READ_SEARCH on newer kernels)",
+            path.display()
+        ))
+    } else {
+        HardwarePluginError::MeasurementError(format!("Failed to read {}: {}", path.display(), err))
+    }
+}
+
+/// Outcome of probing RAPL availability. Kept distinct from a plain `bool` so a host
+/// with the powercap tree present but unreadable (needs root / `CAP_DAC_READ_SEARCH`)
+/// isn't indistinguishable from one with no RAPL hardware at all — the same distinction
+/// `read_measurements` already surfaces via `HardwarePluginError::PermissionDenied`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaplAvailability {
+    /// The powercap root doesn't exist, or no RAPL domain directories were found under it.
+    Unavailable,
+    /// At least one RAPL domain directory exists, but its `energy_uj` counter couldn't
+    /// be read because of a permission error.
+    PermissionDenied,
+    /// At least one RAPL domain's `energy_uj` counter was read successfully.
+    Available,
+}
+
+/// Probe RAPL availability, distinguishing an absent powercap tree from a present one
+/// whose counters aren't readable (see `RaplAvailability`).
+pub fn rapl_availability() -> RaplAvailability {
+    let root = Path::new(RAPL_ROOT);
+    if !root.exists() {
+        return RaplAvailability::Unavailable;
+    }
+
+    let dirs = match rapl_domain_dirs(root) {
+        Ok(dirs) => dirs,
+        Err(_) => return RaplAvailability::Unavailable,
+    };
+
+    let mut saw_permission_denied = false;
+    for dir in &dirs {
+        match fs::read_to_string(dir.join("energy_uj")) {
+            Ok(_) => return RaplAvailability::Available,
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                saw_permission_denied = true;
+            }
+            Err(_) => {}
+        }
+    }
+
+    if saw_permission_denied {
+        RaplAvailability::PermissionDenied
+    } else {
+        RaplAvailability::Unavailable
+    }
+}
+
+/// Check if Intel RAPL is available on the system.
+///
+/// Returns true only if the powercap root exists and at least one domain's
+/// `energy_uj` counter is actually readable. Collapses `RaplAvailability::PermissionDenied`
+/// to `false` for this yes/no check — a caller that needs to tell "absent" apart from
+/// "present but denied" should call `rapl_availability` directly.
 pub fn is_rapl_available() -> bool {
-    // TODO: Implement RAPL availability check
-    false
-} 
\ No newline at end of file
+    rapl_availability() == RaplAvailability::Available
+}
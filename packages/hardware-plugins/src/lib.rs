@@ -3,11 +3,20 @@
 //! This module provides plugins for different hardware platforms to measure
 //! energy consumption.
 
+#[cfg(feature = "intel-rapl")]
 pub mod intel;
 pub mod amd;
+#[cfg(feature = "arm-pmu")]
 pub mod arm;
 pub mod common;
+pub mod estimation;
+pub mod host_metrics;
+#[cfg(feature = "nvidia-gpu")]
 pub mod nvidia;
+pub mod plugins;
+pub mod source;
+pub mod testing;
+pub mod thermal;
 
 use thiserror::Error;
 use std::time::Instant;
@@ -17,11 +26,17 @@ use std::fmt;
 use std::error::Error;
 use std::time::Duration;
 
+#[cfg(feature = "intel-rapl")]
 pub use intel::IntelRaplPlugin;
-pub use amd::AmdEnergyPlugin;
-pub use arm::ArmEnergyPlugin;
+pub use amd::{AmdEnergyPlugin, AmdGpuPlugin};
+#[cfg(feature = "arm-pmu")]
+pub use arm::{ArmEnergyPlugin, ArmThermalPlugin};
+pub use estimation::EstimationPlugin;
+pub use host_metrics::{HostMetrics, HostMetricsPlugin};
+#[cfg(feature = "nvidia-gpu")]
 pub use nvidia::NvidiaGpuPlugin;
-pub use common::HardwarePlugin;
+pub use common::{HardwarePlugin, ThermalPlugin};
+pub use thermal::LinuxThermalPlugin;
 
 /// Errors that can occur during hardware plugin operations
 #[derive(Error, Debug)]
@@ -34,6 +49,9 @@ pub enum HardwarePluginError {
     
     #[error("Unsupported hardware: {0}")]
     UnsupportedHardware(String),
+
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
 }
 
 /// Initialize hardware plugins
@@ -43,9 +61,15 @@ pub fn init() -> Result<(), HardwarePluginError> {
 }
 
 /// Get available hardware plugins
+///
+/// Delegates to `source::available_sources`, which probes every platform-gated
+/// `EnergySource` (and the portable battery-discharge fallback) and returns only
+/// the ones whose `is_supported()` check passes on this host.
 pub fn get_available_plugins() -> Vec<String> {
-    // TODO: Implement plugin discovery
-    Vec::new()
+    source::available_sources()
+        .iter()
+        .map(|s| s.name().to_string())
+        .collect()
 }
 
 /// Represents energy measurement data
@@ -62,6 +86,28 @@ pub fn get_available_plugins() -> Vec<String> {
 pub struct Measurement {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub joules: f64,
+    /// Label for the measurement source, e.g. `"package-0"`, `"dram"`, or a plugin name.
+    /// Lets sessions that aggregate multiple domains (RAPL package + DRAM, CPU + GPU, ...)
+    /// keep each series separate instead of collapsing them into one total.
+    pub source: String,
+    /// The value `joules` wraps back to zero at, if this source is backed by a
+    /// fixed-width hardware counter (e.g. a RAPL `energy_uj` register, read from its
+    /// `max_energy_range_uj` sysfs file). `None` for sources that aren't counter-based
+    /// and so can't wrap, e.g. an instantaneous power reading.
+    pub max_joules: Option<f64>,
+}
+
+/// A single temperature reading, parallel to `Measurement` but for die/ambient
+/// temperature instead of energy. Kept as its own type (rather than a field on
+/// `Measurement`) since not every energy source has a paired thermal sensor, and not
+/// every thermal sensor has a paired energy source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThermalReading {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub celsius: f64,
+    /// Label for the sensor, e.g. a thermal zone's `type` (`"x86_pkg_temp"`, `"cpu-thermal"`)
+    /// or a plugin name, mirroring `Measurement::source`.
+    pub sensor: String,
 }
 
 /// Represents errors that can occur during hardware measurements
@@ -89,32 +135,109 @@ impl PluginRegistry {
         let mut registry = Self {
             plugins: Vec::new(),
         };
+        #[cfg(feature = "intel-rapl")]
         if let Ok(plugin) = IntelRaplPlugin::new() {
             registry.register_plugin(Box::new(plugin));
         }
         if let Ok(plugin) = AmdEnergyPlugin::new() {
             registry.register_plugin(Box::new(plugin));
         }
+        if let Ok(plugin) = AmdGpuPlugin::new() {
+            registry.register_plugin(Box::new(plugin));
+        }
+        #[cfg(feature = "arm-pmu")]
         if let Ok(plugin) = ArmEnergyPlugin::new() {
             registry.register_plugin(Box::new(plugin));
         }
+        #[cfg(feature = "nvidia-gpu")]
         if let Ok(plugin) = NvidiaGpuPlugin::new() {
             registry.register_plugin(Box::new(plugin));
         }
+
+        // No hardware-counter plugin works on this host (e.g. a non-Intel laptop, a VM
+        // with no RAPL passthrough) — fall back to estimating energy from CPU
+        // utilization rather than leaving the registry empty.
+        if !registry.plugins.iter().any(|p| p.is_supported()) {
+            if let Ok(plugin) = EstimationPlugin::new() {
+                registry.register_plugin(Box::new(plugin));
+            }
+        }
+
+        // Not an energy source, so registered unconditionally after the estimation
+        // fallback decision above — it reports `is_supported() == true` unconditionally
+        // and would otherwise short-circuit that check, masking a host with no real
+        // energy counter as if it had one.
+        registry.register_plugin(Box::new(HostMetricsPlugin::new()));
+
         registry
     }
-    
+
+    /// Like `new`, but instead of registering every compiled-in plugin unconditionally
+    /// and leaving callers to filter, only registers the ones whose `is_supported()`
+    /// check actually passes on this host — e.g. skipping a compiled-in Intel RAPL
+    /// plugin on an ARM VM with no `/sys/class/powercap` at all. Falls back to
+    /// `EstimationPlugin` the same way `new` does if nothing else is supported. Call
+    /// `capabilities()` on the result to report which energy sources turned out to be
+    /// live, e.g. so the VSCode integration can tell the user why nothing is being
+    /// measured instead of silently reporting zero.
+    pub fn detect_available() -> Self {
+        let mut registry = Self {
+            plugins: Vec::new(),
+        };
+        #[cfg(feature = "intel-rapl")]
+        if let Ok(plugin) = IntelRaplPlugin::new() {
+            if plugin.is_supported() {
+                registry.register_plugin(Box::new(plugin));
+            }
+        }
+        if let Ok(plugin) = AmdEnergyPlugin::new() {
+            if plugin.is_supported() {
+                registry.register_plugin(Box::new(plugin));
+            }
+        }
+        if let Ok(plugin) = AmdGpuPlugin::new() {
+            if plugin.is_supported() {
+                registry.register_plugin(Box::new(plugin));
+            }
+        }
+        #[cfg(feature = "arm-pmu")]
+        if let Ok(plugin) = ArmEnergyPlugin::new() {
+            if plugin.is_supported() {
+                registry.register_plugin(Box::new(plugin));
+            }
+        }
+        #[cfg(feature = "nvidia-gpu")]
+        if let Ok(plugin) = NvidiaGpuPlugin::new() {
+            if plugin.is_supported() {
+                registry.register_plugin(Box::new(plugin));
+            }
+        }
+
+        if registry.plugins.is_empty() {
+            if let Ok(plugin) = EstimationPlugin::new() {
+                registry.register_plugin(Box::new(plugin));
+            }
+        }
+
+        // See `new`'s matching comment: registered after the estimation fallback
+        // decision so an always-supported host-metrics plugin can't mask the absence
+        // of a real energy source.
+        registry.register_plugin(Box::new(HostMetricsPlugin::new()));
+
+        registry
+    }
+
     pub fn register_plugin(&mut self, plugin: Box<dyn HardwarePlugin>) {
         self.plugins.push(plugin);
     }
-    
+
     pub fn get_available_plugins(&self) -> Vec<&dyn HardwarePlugin> {
         self.plugins.iter()
             .filter(|p| p.is_available())
             .map(|p| p.as_ref())
             .collect()
     }
-    
+
     pub fn get_plugin_by_name(&self, name: &str) -> Option<&dyn HardwarePlugin> {
         self.plugins.iter()
             .find(|p| p.name() == name)
@@ -124,4 +247,16 @@ impl PluginRegistry {
     pub fn get_plugins(&self) -> &[Box<dyn HardwarePlugin>] {
         &self.plugins
     }
+
+    /// Names of the plugins actually registered, for a caller to report which energy
+    /// sources are live on this host. Most useful on a registry built via
+    /// `detect_available`, where every registered plugin already passed `is_supported()`.
+    /// The always-registered `HostMetricsPlugin` is not an energy source and is left out.
+    pub fn capabilities(&self) -> Vec<&'static str> {
+        self.plugins
+            .iter()
+            .map(|p| p.name())
+            .filter(|name| *name != host_metrics::HOST_METRICS_SOURCE)
+            .collect()
+    }
 }
\ No newline at end of file
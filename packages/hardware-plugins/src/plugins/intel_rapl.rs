@@ -1,35 +1,87 @@
+use std::collections::HashMap;
 use std::fs;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 use std::time::Instant;
+use super::units::{MetricUnit, Unit};
 use super::{HardwarePlugin, HardwareError, Measurement};
 
-const MSR_POWER_UNIT: u32 = 0x606;
-const MSR_PKG_ENERGY_STATUS: u32 = 0x611;
-const MSR_DRAM_ENERGY_STATUS: u32 = 0x619;
+/// The units `IntelRAPLPlugin` reports its `additional_metrics` in, declared once and
+/// looked up by `build_metrics` instead of re-deciding the unit at every call site.
+const METRIC_UNITS: &[MetricUnit] = &[
+    MetricUnit { name: "pkg_energy_joules", unit: Unit::Joules },
+    MetricUnit { name: "dram_energy_joules", unit: Unit::Joules },
+    MetricUnit { name: "avg_power_watts", unit: Unit::Watts },
+];
 
-pub struct IntelRAPLPlugin {
-    msr_path: String,
-    power_units: f64,
-    energy_units: f64,
-    time_units: f64,
-    last_measurement: Option<Measurement>,
-    measurement_start: Option<Instant>,
+/// Build a typed `additional_metrics` map from `(name, value)` pairs, looking up each
+/// name's unit in `METRIC_UNITS`.
+fn build_metrics(values: &[(&'static str, f64)]) -> HashMap<String, (f64, Unit)> {
+    values
+        .iter()
+        .map(|(name, value)| {
+            let unit = METRIC_UNITS
+                .iter()
+                .find(|m| m.name == *name)
+                .unwrap_or_else(|| panic!("metric `{name}` is not declared in METRIC_UNITS"))
+                .unit;
+            (name.to_string(), (*value, unit))
+        })
+        .collect()
 }
 
-impl IntelRAPLPlugin {
-    pub fn new() -> Self {
-        Self {
-            msr_path: String::from("/dev/cpu/0/msr"),
-            power_units: 0.0,
-            energy_units: 0.0,
-            time_units: 0.0,
-            last_measurement: None,
-            measurement_start: None,
-        }
+pub const MSR_POWER_UNIT: u32 = 0x606;
+pub const MSR_PKG_ENERGY_STATUS: u32 = 0x611;
+pub const MSR_DRAM_ENERGY_STATUS: u32 = 0x619;
+
+/// The RAPL energy status MSRs are 32-bit counters that wrap around at this value
+/// (in raw energy units, i.e. before scaling by `energy_units`).
+const ENERGY_COUNTER_WRAP: u64 = 1 << 32;
+
+/// Compute the energy delta between two raw RAPL counter reads, accounting for the
+/// 32-bit wraparound: if the counter has wrapped since `last`, `current` will be
+/// smaller than `last`, in which case the counter travelled `(WRAP - last) + current`
+/// raw units rather than going backwards.
+fn energy_counter_delta(current: u64, last: u64) -> u64 {
+    if current >= last {
+        current - last
+    } else {
+        (ENERGY_COUNTER_WRAP - last) + current
+    }
+}
+
+/// A readable source of raw MSR register values. Abstracts over the real
+/// `/dev/cpu/0/msr` character device so `IntelRAPLPlugin` can be driven in-process
+/// against a simulated backend (see `hardware-plugins-test-support::MockMsrSource`)
+/// without requiring root or the `msr` kernel module.
+pub trait MsrSource: Send + Sync {
+    /// Read the 8-byte value of the given MSR register.
+    fn read_msr(&self, msr: u32) -> Result<u64, HardwareError>;
+
+    /// Whether this source is currently backed by something readable.
+    fn is_present(&self) -> bool;
+}
+
+/// The real MSR source, reading `/dev/cpu/0/msr` via `pread`-style seek + read.
+pub struct FileMsrSource {
+    path: String,
+}
+
+impl FileMsrSource {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
     }
+}
+
+impl Default for FileMsrSource {
+    fn default() -> Self {
+        Self::new("/dev/cpu/0/msr")
+    }
+}
 
+impl MsrSource for FileMsrSource {
     fn read_msr(&self, msr: u32) -> Result<u64, HardwareError> {
-        let path = Path::new(&self.msr_path);
+        let path = Path::new(&self.path);
         if !path.exists() {
             return Err(HardwareError::DeviceNotFound(
                 "MSR device not found. Make sure msr module is loaded.".to_string(),
@@ -39,6 +91,9 @@ impl IntelRAPLPlugin {
         let mut file = fs::File::open(path)
             .map_err(|e| HardwareError::PermissionDenied(format!("Failed to open MSR: {}", e)))?;
 
+        file.seek(SeekFrom::Start(msr as u64))
+            .map_err(|e| HardwareError::SensorError(format!("Failed to seek to MSR {:#x}: {}", msr, e)))?;
+
         let mut buffer = [0u8; 8];
         file.read_exact(&mut buffer)
             .map_err(|e| HardwareError::SensorError(format!("Failed to read MSR: {}", e)))?;
@@ -46,19 +101,75 @@ impl IntelRAPLPlugin {
         Ok(u64::from_le_bytes(buffer))
     }
 
+    fn is_present(&self) -> bool {
+        Path::new(&self.path).exists()
+    }
+}
+
+/// The raw counter reading taken when a measurement window opens, so the matching
+/// `stop_measurement` can compute the energy delta across the window.
+struct AccountingWindow {
+    start: Instant,
+    pkg_raw: u64,
+    dram_raw: u64,
+}
+
+pub struct IntelRAPLPlugin<S: MsrSource = FileMsrSource> {
+    source: S,
+    power_units: f64,
+    energy_units: f64,
+    time_units: f64,
+    last_measurement: Option<Measurement>,
+    measurement_start: Option<Instant>,
+    accounting_window: Option<AccountingWindow>,
+}
+
+impl IntelRAPLPlugin<FileMsrSource> {
+    pub fn new() -> Self {
+        Self::with_source(FileMsrSource::default())
+    }
+}
+
+impl<S: MsrSource> IntelRAPLPlugin<S> {
+    /// Build a plugin over an arbitrary `MsrSource`, e.g. a `MockMsrSource` in tests.
+    pub fn with_source(source: S) -> Self {
+        Self {
+            source,
+            power_units: 0.0,
+            energy_units: 0.0,
+            time_units: 0.0,
+            last_measurement: None,
+            measurement_start: None,
+            accounting_window: None,
+        }
+    }
+
+    fn read_msr(&self, msr: u32) -> Result<u64, HardwareError> {
+        self.source.read_msr(msr)
+    }
+
+    /// Decode the power/energy/time unit fields packed into the raw `MSR_POWER_UNIT`
+    /// register into their floating-point scaling factors. Pulled out as a pure
+    /// function so tests can verify the bit-scaling math directly against known
+    /// register values without needing a real MSR device.
+    pub fn decode_power_units(power_unit: u64) -> (f64, f64, f64) {
+        let power_units = 1.0 / (1u64 << (power_unit & 0xF)) as f64;
+        let energy_units = 1.0 / (1u64 << ((power_unit >> 8) & 0x1F)) as f64;
+        let time_units = 1.0 / (1u64 << ((power_unit >> 16) & 0xF)) as f64;
+        (power_units, energy_units, time_units)
+    }
+
     fn read_power_unit(&mut self) -> Result<(), HardwareError> {
         let power_unit = self.read_msr(MSR_POWER_UNIT)?;
-        
-        // Extract power, energy, and time units from the MSR
-        self.power_units = 1.0 / (1u64 << ((power_unit >> 0) & 0xF)) as f64;
-        self.energy_units = 1.0 / (1u64 << ((power_unit >> 8) & 0x1F)) as f64;
-        self.time_units = 1.0 / (1u64 << ((power_unit >> 16) & 0xF)) as f64;
-        
+        let (power_units, energy_units, time_units) = Self::decode_power_units(power_unit);
+        self.power_units = power_units;
+        self.energy_units = energy_units;
+        self.time_units = time_units;
         Ok(())
     }
 }
 
-impl HardwarePlugin for IntelRAPLPlugin {
+impl<S: MsrSource> HardwarePlugin for IntelRAPLPlugin<S> {
     fn initialize(&mut self) -> Result<(), HardwareError> {
         self.read_power_unit()?;
         Ok(())
@@ -73,7 +184,7 @@ impl HardwarePlugin for IntelRAPLPlugin {
     }
 
     fn is_available(&self) -> bool {
-        Path::new(&self.msr_path).exists()
+        self.source.is_present()
     }
 
     fn start_measurement(&mut self) -> Result<(), HardwareError> {
@@ -82,7 +193,15 @@ impl HardwarePlugin for IntelRAPLPlugin {
                 "Measurement already in progress".to_string(),
             ));
         }
-        self.measurement_start = Some(Instant::now());
+        let pkg_raw = self.read_msr(MSR_PKG_ENERGY_STATUS)?;
+        let dram_raw = self.read_msr(MSR_DRAM_ENERGY_STATUS)?;
+        let start = Instant::now();
+        self.measurement_start = Some(start);
+        self.accounting_window = Some(AccountingWindow {
+            start,
+            pkg_raw,
+            dram_raw,
+        });
         Ok(())
     }
 
@@ -93,27 +212,55 @@ impl HardwarePlugin for IntelRAPLPlugin {
             ));
         }
         self.measurement_start = None;
-        Ok(())
-    }
 
-    fn get_measurement(&self) -> Result<Measurement, HardwareError> {
-        let pkg_energy = self.read_msr(MSR_PKG_ENERGY_STATUS)?;
-        let dram_energy = self.read_msr(MSR_DRAM_ENERGY_STATUS)?;
+        let window = self.accounting_window.take().ok_or_else(|| {
+            HardwareError::UnsupportedOperation("No measurement in progress".to_string())
+        })?;
 
-        let mut additional_metrics = std::collections::HashMap::new();
-        additional_metrics.insert("dram_energy_joules".to_string(), dram_energy as f64 * self.energy_units);
+        let pkg_end = self.read_msr(MSR_PKG_ENERGY_STATUS)?;
+        let dram_end = self.read_msr(MSR_DRAM_ENERGY_STATUS)?;
+        let elapsed_secs = window.start.elapsed().as_secs_f64();
+
+        let pkg_joules =
+            energy_counter_delta(pkg_end, window.pkg_raw) as f64 * self.energy_units;
+        let dram_joules =
+            energy_counter_delta(dram_end, window.dram_raw) as f64 * self.energy_units;
+        let avg_power_watts = if elapsed_secs > 0.0 {
+            pkg_joules / elapsed_secs
+        } else {
+            0.0
+        };
 
-        let measurement = Measurement {
+        let additional_metrics = build_metrics(&[
+            ("pkg_energy_joules", pkg_joules),
+            ("dram_energy_joules", dram_joules),
+            ("avg_power_watts", avg_power_watts),
+        ]);
+
+        self.last_measurement = Some(Measurement {
             timestamp: Instant::now(),
-            power_watts: pkg_energy as f64 * self.power_units,
+            power_watts: avg_power_watts,
             temperature_celsius: None, // RAPL doesn't provide temperature
             additional_metrics,
-        };
+        });
+
+        Ok(())
+    }
 
-        Ok(measurement)
+    fn get_measurement(&self) -> Result<Measurement, HardwareError> {
+        self.last_measurement.clone().ok_or_else(|| {
+            HardwareError::UnsupportedOperation(
+                "No measurement available; call start_measurement and stop_measurement first"
+                    .to_string(),
+            )
+        })
     }
 
     fn supported_metrics(&self) -> Vec<&'static str> {
-        vec!["power_watts", "dram_energy_joules"]
+        vec!["power_watts", "pkg_energy_joules", "dram_energy_joules", "avg_power_watts"]
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+#[path = "intel_rapl_test.rs"]
+mod tests;
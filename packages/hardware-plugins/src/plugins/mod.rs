@@ -0,0 +1,38 @@
+//! Alternate MSR-register-based RAPL plugin, kept separate from the sysfs-based
+//! `crate::intel::IntelRaplPlugin`. This family reads raw Model-Specific Registers via
+//! `/dev/cpu/0/msr` instead of the powercap tree, which needs its own `Measurement`
+//! shape (instantaneous power/temperature rather than a cumulative joule counter) and
+//! its own synchronous `HardwarePlugin` trait.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+pub use crate::HardwareError;
+
+pub mod intel_rapl;
+pub mod units;
+
+use units::Unit;
+
+/// A measurement result specific to the MSR-based plugins in this module. Each
+/// `additional_metrics` entry carries its value alongside the `Unit` it was reported
+/// in (see `crate::plugins::units`), rather than smuggling the unit into the key name.
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub timestamp: Instant,
+    pub power_watts: f64,
+    pub temperature_celsius: Option<f64>,
+    pub additional_metrics: HashMap<String, (f64, Unit)>,
+}
+
+/// Synchronous hardware plugin trait used by the MSR-based plugins in this module.
+pub trait HardwarePlugin {
+    fn initialize(&mut self) -> Result<(), HardwareError>;
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn is_available(&self) -> bool;
+    fn start_measurement(&mut self) -> Result<(), HardwareError>;
+    fn stop_measurement(&mut self) -> Result<(), HardwareError>;
+    fn get_measurement(&self) -> Result<Measurement, HardwareError>;
+    fn supported_metrics(&self) -> Vec<&'static str>;
+}
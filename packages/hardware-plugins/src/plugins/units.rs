@@ -0,0 +1,109 @@
+//! Typed units for plugin metrics. `Measurement::additional_metrics` used to be a
+//! `HashMap<String, f64>` where the unit was smuggled into the key (e.g.
+//! `"dram_energy_joules"`), forcing every consumer to string-parse the metric name.
+//! Instead, each entry now carries an explicit `(value, unit)` pair.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A physical unit a plugin metric can be reported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Joules,
+    Watts,
+    Celsius,
+    Percent,
+    Hertz,
+}
+
+/// Returned when a unit spec string doesn't match a known `Unit`.
+#[derive(Debug, Error)]
+#[error("unknown unit: {0}")]
+pub struct UnknownConversion(pub String);
+
+impl FromStr for Unit {
+    type Err = UnknownConversion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "joules" => Ok(Unit::Joules),
+            "watts" => Ok(Unit::Watts),
+            "celsius" => Ok(Unit::Celsius),
+            "percent" => Ok(Unit::Percent),
+            "hertz" => Ok(Unit::Hertz),
+            other => Err(UnknownConversion(other.to_string())),
+        }
+    }
+}
+
+/// Convert joules to watt-hours (1 Wh = 3600 J).
+pub fn joules_to_watt_hours(joules: f64) -> f64 {
+    joules / 3600.0
+}
+
+/// Convert watt-hours to joules.
+pub fn watt_hours_to_joules(watt_hours: f64) -> f64 {
+    watt_hours * 3600.0
+}
+
+/// Convert an average power reading sustained over `seconds` into the energy it
+/// represents.
+pub fn watts_times_seconds_to_joules(watts: f64, seconds: f64) -> f64 {
+    watts * seconds
+}
+
+/// A metric name a plugin declares, paired with the unit it reports values in. A
+/// plugin builds a `&'static [MetricUnit]` once (see `intel_rapl::METRIC_UNITS`)
+/// instead of re-deriving the unit from the metric's name string at every call site.
+#[derive(Debug, Clone, Copy)]
+pub struct MetricUnit {
+    pub name: &'static str,
+    pub unit: Unit,
+}
+
+/// Sum only the energy-typed (`Unit::Joules`) entries of a metrics map. Mismatched
+/// units (e.g. a temperature or a power reading) are excluded rather than silently
+/// added as plain `f64`s.
+pub fn total_energy_joules(metrics: &HashMap<String, (f64, Unit)>) -> f64 {
+    metrics
+        .values()
+        .filter(|(_, unit)| *unit == Unit::Joules)
+        .map(|(value, _)| value)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_units() {
+        assert_eq!(Unit::from_str("joules").unwrap(), Unit::Joules);
+        assert_eq!(Unit::from_str("watts").unwrap(), Unit::Watts);
+        assert_eq!(Unit::from_str("celsius").unwrap(), Unit::Celsius);
+        assert_eq!(Unit::from_str("percent").unwrap(), Unit::Percent);
+        assert_eq!(Unit::from_str("hertz").unwrap(), Unit::Hertz);
+    }
+
+    #[test]
+    fn rejects_unknown_units() {
+        assert!(Unit::from_str("lumens").is_err());
+    }
+
+    #[test]
+    fn converts_joules_and_watt_hours() {
+        assert!((joules_to_watt_hours(3600.0) - 1.0).abs() < 1e-9);
+        assert!((watt_hours_to_joules(1.0) - 3600.0).abs() < 1e-9);
+        assert!((watts_times_seconds_to_joules(2.0, 10.0) - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sums_only_energy_typed_metrics() {
+        let mut metrics = HashMap::new();
+        metrics.insert("pkg_energy_joules".to_string(), (1.0, Unit::Joules));
+        metrics.insert("dram_energy_joules".to_string(), (0.5, Unit::Joules));
+        metrics.insert("avg_power_watts".to_string(), (3.0, Unit::Watts));
+        assert!((total_energy_joules(&metrics) - 1.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,322 @@
+//! Cross-platform energy source abstraction.
+//!
+//! `HardwarePlugin` implementations like `IntelRaplPlugin`/`NvidiaGpuPlugin` are Linux-only
+//! and report `false`/empty everywhere else, leaving laptops and macOS/Windows hosts with no
+//! measurement path at all. `EnergySource` is a narrower trait for raw energy collectors,
+//! following the sources-vs-collectors split used by `bottom`: each collector owns exactly
+//! one way of getting a joule reading, and platform selection happens via `cfg_if` rather
+//! than runtime branching, so unsupported platforms don't even link the irrelevant backend.
+
+use crate::{HardwareError, Measurement};
+use chrono::Utc;
+use cfg_if::cfg_if;
+#[cfg(target_os = "linux")]
+use std::sync::Mutex;
+#[cfg(target_os = "linux")]
+use std::time::Instant;
+
+/// A single energy measurement collector, narrower than `HardwarePlugin`: no lifecycle,
+/// just "can I read right now, and what does the counter say".
+pub trait EnergySource: Send + Sync {
+    /// Stable identifier for this source, used as the `Measurement::source` label.
+    fn name(&self) -> &'static str;
+
+    /// Whether this collector's backing device/file is present and readable on this host.
+    fn is_supported(&self) -> bool;
+
+    /// Take an instantaneous reading.
+    fn read(&self) -> Result<Measurement, HardwareError>;
+}
+
+cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        mod linux {
+            use super::*;
+
+            /// Wraps the existing `IntelRaplPlugin` sysfs reader as an `EnergySource`.
+            #[cfg(feature = "intel-rapl")]
+            pub struct RaplCollector;
+
+            #[cfg(feature = "intel-rapl")]
+            impl EnergySource for RaplCollector {
+                fn name(&self) -> &'static str {
+                    "intel-rapl"
+                }
+
+                fn is_supported(&self) -> bool {
+                    crate::intel::is_rapl_available()
+                }
+
+                fn read(&self) -> Result<Measurement, HardwareError> {
+                    let plugin = crate::IntelRaplPlugin::new()?;
+                    let measurements = plugin
+                        .read_measurements()
+                        .map_err(|e| HardwareError::SensorError(e.to_string()))?;
+                    measurements
+                        .into_iter()
+                        .find(|m| m.source.starts_with("package"))
+                        .ok_or_else(|| HardwareError::DeviceNotFound("no package RAPL domain".to_string()))
+                }
+            }
+
+            /// Wraps the existing `AmdEnergyPlugin` sysfs reader as an `EnergySource`.
+            /// Unlike Intel RAPL's package/core/uncore/dram split (where `RaplCollector`
+            /// picks out the single "package" domain), every `amd-energy` domain is
+            /// already a whole socket's package counter, each wrapping independently —
+            /// summing them would break wraparound-aware delta math downstream, so this
+            /// reports just the first socket found. A caller that wants every socket
+            /// should call `AmdEnergyPlugin::read_measurements` directly.
+            pub struct AmdEnergyCollector;
+
+            impl EnergySource for AmdEnergyCollector {
+                fn name(&self) -> &'static str {
+                    "amd-energy"
+                }
+
+                fn is_supported(&self) -> bool {
+                    crate::amd::is_amd_energy_available()
+                }
+
+                fn read(&self) -> Result<Measurement, HardwareError> {
+                    let plugin = crate::AmdEnergyPlugin::new()?;
+                    let measurements = plugin
+                        .read_measurements()
+                        .map_err(|e| HardwareError::SensorError(e.to_string()))?;
+                    measurements
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| HardwareError::DeviceNotFound("no amd-energy socket domain".to_string()))
+                }
+            }
+
+            /// Wraps NVML GPU power draw as an `EnergySource`.
+            #[cfg(feature = "nvidia-gpu")]
+            pub struct NvmlCollector;
+
+            #[cfg(feature = "nvidia-gpu")]
+            impl EnergySource for NvmlCollector {
+                fn name(&self) -> &'static str {
+                    "nvidia-nvml"
+                }
+
+                fn is_supported(&self) -> bool {
+                    crate::nvidia::is_nvidia_gpu_available()
+                }
+
+                fn read(&self) -> Result<Measurement, HardwareError> {
+                    let plugin = crate::NvidiaGpuPlugin::new()?;
+                    plugin.get_measurement()
+                }
+            }
+
+            pub fn platform_sources() -> Vec<Box<dyn EnergySource>> {
+                #[allow(unused_mut)]
+                let mut sources: Vec<Box<dyn EnergySource>> = vec![Box::new(AmdEnergyCollector)];
+                #[cfg(feature = "intel-rapl")]
+                sources.push(Box::new(RaplCollector));
+                #[cfg(feature = "nvidia-gpu")]
+                sources.push(Box::new(NvmlCollector));
+                sources
+            }
+        }
+        use linux::platform_sources;
+    } else if #[cfg(target_os = "macos")] {
+        mod macos {
+            use super::*;
+
+            /// Reads system power via IOKit/SMC. No portable sysfs equivalent exists on
+            /// macOS, so this collector is a thin placeholder for the `ioreg`/SMC call
+            /// a real build would shell out to or bind via `IOKit-sys`.
+            pub struct IokitSmcCollector;
+
+            impl EnergySource for IokitSmcCollector {
+                fn name(&self) -> &'static str {
+                    "macos-smc"
+                }
+
+                fn is_supported(&self) -> bool {
+                    // Real detection would probe the SMC power keys (e.g. "PSTR") via IOKit.
+                    false
+                }
+
+                fn read(&self) -> Result<Measurement, HardwareError> {
+                    Err(HardwareError::UnsupportedOperation(
+                        "IOKit/SMC power reading is not yet implemented".to_string(),
+                    ))
+                }
+            }
+
+            pub fn platform_sources() -> Vec<Box<dyn EnergySource>> {
+                vec![Box::new(IokitSmcCollector)]
+            }
+        }
+        use macos::platform_sources;
+    } else {
+        mod other {
+            use super::*;
+
+            pub fn platform_sources() -> Vec<Box<dyn EnergySource>> {
+                Vec::new()
+            }
+        }
+        use other::platform_sources;
+    }
+}
+
+/// Root of the Linux battery power-supply sysfs tree.
+#[cfg(target_os = "linux")]
+const POWER_SUPPLY_ROOT: &str = "/sys/class/power_supply";
+
+/// Portable energy source for laptops: estimates consumption from the battery's
+/// discharge rate rather than a hardware energy counter. Used as a fallback when
+/// no RAPL (or platform-specific) domain is present.
+pub struct BatteryCollector {
+    #[cfg(target_os = "linux")]
+    device_path: String,
+    /// Running total for the `voltage_now * current_now` fallback in `read_joules`,
+    /// integrated over elapsed wall-clock time the same way `AmdGpuPlugin::sample_joules`
+    /// turns an instantaneous power reading into an accumulating joules figure. On the
+    /// `energy_now` path, which is already a counter and needs no integration, only
+    /// `energy_now_baseline_uwh` is used; the running total and its timestamp stay at
+    /// their initial values.
+    #[cfg(target_os = "linux")]
+    state: Mutex<BatteryState>,
+}
+
+#[cfg(target_os = "linux")]
+struct BatteryState {
+    accumulated_joules: f64,
+    last_sample: Instant,
+    /// First `energy_now` reading seen (microwatt-hours of charge remaining), so later
+    /// readings can be reported as energy *consumed* (`baseline - current`, which rises
+    /// as the battery discharges) rather than the raw remaining-charge counter (which
+    /// falls) — keeping this path's sign and quantity consistent with the
+    /// `voltage_now * current_now` fallback below.
+    energy_now_baseline_uwh: Option<f64>,
+}
+
+impl BatteryCollector {
+    /// Probe `/sys/class/power_supply` for the first battery device (`BAT0`, `BAT1`, ...).
+    #[cfg(target_os = "linux")]
+    pub fn discover() -> Option<Self> {
+        let entries = std::fs::read_dir(POWER_SUPPLY_ROOT).ok()?;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let type_path = path.join("type");
+            if std::fs::read_to_string(&type_path).map(|t| t.trim() == "Battery").unwrap_or(false) {
+                return Some(Self {
+                    device_path: path.to_string_lossy().into_owned(),
+                    state: Mutex::new(BatteryState {
+                        accumulated_joules: 0.0,
+                        last_sample: Instant::now(),
+                        energy_now_baseline_uwh: None,
+                    }),
+                });
+            }
+        }
+        None
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn discover() -> Option<Self> {
+        None
+    }
+
+    /// Read the battery's `energy_now` counter (microwatt-hours of charge *remaining*,
+    /// which falls as the battery discharges) and report it as energy *consumed* since
+    /// the first reading (`baseline - current`, which rises instead), converted to
+    /// joules (1 uWh = 3.6e-3 J), so a start→end delta agrees in sign and quantity with
+    /// the `voltage_now * current_now` fallback below rather than reporting the opposite
+    /// of what that fallback does. Falls back to integrating `voltage_now * current_now`
+    /// (instantaneous watts, not joules) over the elapsed time since the last sample if
+    /// `energy_now` is absent (some drivers only expose the instantaneous power path),
+    /// mirroring `AmdGpuPlugin::sample_joules`.
+    #[cfg(target_os = "linux")]
+    fn read_joules(&self) -> Result<f64, HardwareError> {
+        let base = std::path::Path::new(&self.device_path);
+        if let Ok(energy_uwh) = std::fs::read_to_string(base.join("energy_now")) {
+            let uwh: f64 = energy_uwh
+                .trim()
+                .parse()
+                .map_err(|e| HardwareError::SensorError(format!("bad energy_now: {}", e)))?;
+            let mut state = self.state.lock().unwrap();
+            let baseline = *state.energy_now_baseline_uwh.get_or_insert(uwh);
+            let consumed_uwh = (baseline - uwh).max(0.0);
+            return Ok(consumed_uwh * 3.6e-3);
+        }
+
+        let voltage_uv: f64 = std::fs::read_to_string(base.join("voltage_now"))
+            .map_err(|e| HardwareError::SensorError(format!("no energy_now or voltage_now: {}", e)))?
+            .trim()
+            .parse()
+            .map_err(|e| HardwareError::SensorError(format!("bad voltage_now: {}", e)))?;
+        let current_ua: f64 = std::fs::read_to_string(base.join("current_now"))
+            .map_err(|e| HardwareError::SensorError(format!("no current_now: {}", e)))?
+            .trim()
+            .parse()
+            .map_err(|e| HardwareError::SensorError(format!("bad current_now: {}", e)))?;
+
+        // watts = volts * amps; uV * uA = 1e-12 W, so scale to W.
+        let watts = (voltage_uv / 1e6) * (current_ua / 1e6);
+
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed_seconds = now.duration_since(state.last_sample).as_secs_f64();
+        state.last_sample = now;
+        state.accumulated_joules += watts * elapsed_seconds;
+        Ok(state.accumulated_joules)
+    }
+}
+
+impl EnergySource for BatteryCollector {
+    fn name(&self) -> &'static str {
+        "battery-discharge"
+    }
+
+    fn is_supported(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            std::path::Path::new(&self.device_path).join("energy_now").exists()
+                || std::path::Path::new(&self.device_path).join("voltage_now").exists()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
+    fn read(&self) -> Result<Measurement, HardwareError> {
+        #[cfg(target_os = "linux")]
+        {
+            Ok(Measurement {
+                timestamp: Utc::now(),
+                joules: self.read_joules()?,
+                source: self.name().to_string(),
+                max_joules: None,
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Err(HardwareError::UnsupportedOperation(
+                "battery discharge collector is only implemented for Linux".to_string(),
+            ))
+        }
+    }
+}
+
+/// Probe every compiled-in collector's `is_supported()` and return the working set,
+/// preferring hardware energy counters over the battery fallback when both are present.
+pub fn available_sources() -> Vec<Box<dyn EnergySource>> {
+    let mut sources = platform_sources();
+    sources.retain(|s| s.is_supported());
+
+    if sources.is_empty() {
+        if let Some(battery) = BatteryCollector::discover() {
+            if battery.is_supported() {
+                sources.push(Box::new(battery));
+            }
+        }
+    }
+
+    sources
+}
@@ -0,0 +1,258 @@
+//! In-process test support for `HardwarePlugin` implementors.
+//!
+//! The only coverage the real plugins have today is the `mockall`-based `intel_rapl`
+//! test module, and every one of those tests bails out with "Skipping … MSR not
+//! available" outside a machine with real RAPL access. `MockMeasurementPlugin` plays
+//! back a scripted sequence of `Measurement`s instead of reading hardware, and
+//! `PluginTestHarness` drives the full `initialize -> start_measurement ->
+//! get_measurement -> stop_measurement` lifecycle against it on a dedicated OS thread
+//! — the same way the real engine drives a plugin from a thread other than the one
+//! that constructed it — so plugin authors can write deterministic unit tests without
+//! real RAPL/NVML/PMU hardware.
+
+use crate::common::BasePlugin;
+use crate::{HardwareError, HardwarePlugin, Measurement};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+
+struct MockState {
+    script: VecDeque<Measurement>,
+    started: bool,
+    last_joules: Option<f64>,
+}
+
+/// A `HardwarePlugin` that plays back a predetermined sequence of `Measurement`s
+/// instead of reading real hardware, enforcing the same lifecycle invariants a real
+/// plugin is expected to: `start_measurement` errors if already started,
+/// `stop_measurement` errors if never started, and `get_measurement` errors if the
+/// script reports a lower joule count than the previous reading (a real monotonic
+/// hardware counter never goes backwards outside of a documented wraparound, which
+/// this mock doesn't model).
+pub struct MockMeasurementPlugin {
+    base: BasePlugin,
+    state: Mutex<MockState>,
+}
+
+impl MockMeasurementPlugin {
+    /// Play back exactly these measurements, one per `get_measurement` call (whether
+    /// reached via `start_measurement`, `stop_measurement`, or directly).
+    pub fn new(name: &'static str, script: Vec<Measurement>) -> Self {
+        Self {
+            base: BasePlugin::new(name, "Scripted test plugin for PluginTestHarness", String::new()),
+            state: Mutex::new(MockState {
+                script: script.into(),
+                started: false,
+                last_joules: None,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl HardwarePlugin for MockMeasurementPlugin {
+    fn name(&self) -> &'static str {
+        self.base.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.base.description()
+    }
+
+    fn is_available(&self) -> bool {
+        self.base.is_enabled()
+    }
+
+    fn is_supported(&self) -> bool {
+        true
+    }
+
+    fn initialize(&mut self) -> Result<(), HardwareError> {
+        Ok(())
+    }
+
+    async fn start_measurement(&self) -> Result<Measurement, HardwareError> {
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.started {
+                return Err(HardwareError::Other(format!(
+                    "MockMeasurementPlugin \"{}\" start_measurement called twice without an intervening stop_measurement",
+                    self.base.name()
+                )));
+            }
+            state.started = true;
+        }
+        self.get_measurement()
+    }
+
+    async fn stop_measurement(&self) -> Result<Measurement, HardwareError> {
+        {
+            let mut state = self.state.lock().unwrap();
+            if !state.started {
+                return Err(HardwareError::Other(format!(
+                    "MockMeasurementPlugin \"{}\" stop_measurement called without a preceding start_measurement",
+                    self.base.name()
+                )));
+            }
+            state.started = false;
+        }
+        self.get_measurement()
+    }
+
+    fn get_measurement(&self) -> Result<Measurement, HardwareError> {
+        let mut state = self.state.lock().unwrap();
+        let measurement = state.script.pop_front().ok_or_else(|| {
+            HardwareError::Other(format!("MockMeasurementPlugin \"{}\" script exhausted", self.base.name()))
+        })?;
+
+        if let Some(last) = state.last_joules {
+            if measurement.joules < last {
+                return Err(HardwareError::Other(format!(
+                    "MockMeasurementPlugin \"{}\" script is non-monotonic: {} reported after {}",
+                    self.base.name(),
+                    measurement.joules,
+                    last
+                )));
+            }
+        }
+        state.last_joules = Some(measurement.joules);
+
+        Ok(measurement)
+    }
+
+    fn supported_metrics(&self) -> Vec<&'static str> {
+        vec!["energy"]
+    }
+
+    async fn get_total_energy_consumption(&self) -> Result<f64, HardwareError> {
+        Ok(self.state.lock().unwrap().last_joules.unwrap_or(0.0))
+    }
+}
+
+/// Drives a `HardwarePlugin` through its lifecycle on a dedicated OS thread, so a
+/// plugin's internal state and `PluginRegistry` wiring are exercised the same way the
+/// real engine exercises them — from a thread other than the one that constructed the
+/// plugin — without needing real hardware underneath.
+pub struct PluginTestHarness;
+
+/// Block the current thread on a single future, for the harness's synchronous assert
+/// helpers that don't need the full separate-thread treatment `run_lifecycle` gives.
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Runtime::new()
+        .expect("failed to build a runtime for the plugin test harness")
+        .block_on(future)
+}
+
+impl PluginTestHarness {
+    /// Run `initialize -> start_measurement -> get_measurement -> stop_measurement` on
+    /// a separate thread and return every measurement observed, in call order
+    /// (`[start, get, stop]`), or the first error encountered.
+    pub fn run_lifecycle(
+        mut plugin: impl HardwarePlugin + 'static,
+    ) -> Result<Vec<Measurement>, HardwareError> {
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new()
+                .expect("failed to build a runtime for the plugin test thread");
+            runtime.block_on(async move {
+                plugin.initialize()?;
+                let start = plugin.start_measurement().await?;
+                let get = plugin.get_measurement()?;
+                let stop = plugin.stop_measurement().await?;
+                Ok(vec![start, get, stop])
+            })
+        })
+        .join()
+        .expect("plugin test thread panicked")
+    }
+
+    /// Assert that calling `start_measurement` twice in a row, without an intervening
+    /// `stop_measurement`, surfaces an error instead of silently restarting.
+    pub fn assert_double_start_errors(plugin: &(impl HardwarePlugin + Sync)) {
+        let first = block_on(plugin.start_measurement());
+        assert!(first.is_ok(), "first start_measurement should succeed");
+        let second = block_on(plugin.start_measurement());
+        assert!(
+            second.is_err(),
+            "a second start_measurement without an intervening stop_measurement should error"
+        );
+    }
+
+    /// Assert that calling `stop_measurement` without a preceding `start_measurement`
+    /// surfaces an error instead of returning a bogus measurement.
+    pub fn assert_stop_without_start_errors(plugin: &(impl HardwarePlugin + Sync)) {
+        let result = block_on(plugin.stop_measurement());
+        assert!(
+            result.is_err(),
+            "stop_measurement without a preceding start_measurement should error"
+        );
+    }
+
+    /// Assert that `joules` never decreases across a sequence of measurements taken in
+    /// order, the invariant every real hardware counter upholds outside of a
+    /// documented wraparound.
+    pub fn assert_monotonic_joules(measurements: &[Measurement]) {
+        for pair in measurements.windows(2) {
+            assert!(
+                pair[1].joules >= pair[0].joules,
+                "joules regressed from {} to {} between consecutive measurements",
+                pair[0].joules,
+                pair[1].joules
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn measurement(joules: f64) -> Measurement {
+        Measurement {
+            timestamp: Utc::now(),
+            joules,
+            source: "mock".to_string(),
+            max_joules: None,
+        }
+    }
+
+    #[test]
+    fn run_lifecycle_returns_measurements_in_call_order() {
+        let plugin = MockMeasurementPlugin::new(
+            "mock",
+            vec![measurement(1.0), measurement(2.0), measurement(3.0)],
+        );
+
+        let measurements = PluginTestHarness::run_lifecycle(plugin).unwrap();
+
+        assert_eq!(measurements.len(), 3);
+        assert_eq!(measurements[0].joules, 1.0);
+        assert_eq!(measurements[1].joules, 2.0);
+        assert_eq!(measurements[2].joules, 3.0);
+    }
+
+    #[test]
+    fn double_start_without_stop_errors() {
+        let plugin = MockMeasurementPlugin::new("mock", vec![measurement(1.0), measurement(2.0)]);
+        PluginTestHarness::assert_double_start_errors(&plugin);
+    }
+
+    #[test]
+    fn stop_without_start_errors() {
+        let plugin = MockMeasurementPlugin::new("mock", vec![measurement(1.0)]);
+        PluginTestHarness::assert_stop_without_start_errors(&plugin);
+    }
+
+    #[test]
+    fn non_monotonic_script_errors_on_get_measurement() {
+        let plugin = MockMeasurementPlugin::new("mock", vec![measurement(5.0), measurement(1.0)]);
+        assert!(plugin.get_measurement().is_ok());
+        assert!(plugin.get_measurement().is_err());
+    }
+
+    #[test]
+    fn monotonic_joules_assertion_passes_for_a_non_decreasing_sequence() {
+        PluginTestHarness::assert_monotonic_joules(&[measurement(1.0), measurement(2.0), measurement(2.0)]);
+    }
+}
@@ -0,0 +1,150 @@
+//! Linux thermal zone plugin
+//!
+//! Reads every `/sys/class/thermal/thermal_zone*/temp` on the host. Unlike the
+//! vendor-specific energy plugins (`IntelRaplPlugin`, `ArmEnergyPlugin`, ...), thermal
+//! zones are exposed generically by the kernel regardless of CPU vendor, so this plugin
+//! isn't tied to a particular architecture.
+
+use crate::HardwarePluginError;
+use crate::{HardwareError, ThermalReading};
+use crate::common::{BasePlugin, DefaultThermalPluginImpl, ThermalPlugin};
+use chrono;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Root of the Linux thermal sysfs tree.
+const THERMAL_ROOT: &str = "/sys/class/thermal";
+
+/// Linux thermal zone plugin
+pub struct LinuxThermalPlugin {
+    base: BasePlugin,
+}
+
+impl LinuxThermalPlugin {
+    /// Create a new Linux thermal zone plugin
+    pub fn new() -> Result<Self, HardwareError> {
+        Ok(Self {
+            base: BasePlugin::new(
+                "linux-thermal",
+                "Linux thermal zone temperature plugin",
+                THERMAL_ROOT.to_string(),
+            ),
+        })
+    }
+
+    /// Read temperature readings from every thermal zone
+    ///
+    /// Enumerates every `thermal_zone*` directory under `/sys/class/thermal` and emits
+    /// one `ThermalReading` per zone, labeled with the zone's `type` file
+    /// (`"x86_pkg_temp"`, `"acpitz"`, `"cpu-thermal"`, ...).
+    pub fn read_readings(&self) -> Result<Vec<ThermalReading>, HardwarePluginError> {
+        let root = Path::new(self.base.device_path());
+        if !root.exists() {
+            return Err(HardwarePluginError::UnsupportedHardware(
+                "Linux thermal sysfs tree not found".to_string(),
+            ));
+        }
+
+        let mut readings = Vec::new();
+        for zone_dir in thermal_zone_dirs(root)? {
+            readings.push(read_zone_reading(&zone_dir)?);
+        }
+
+        if readings.is_empty() {
+            return Err(HardwarePluginError::UnsupportedHardware(
+                "No readable thermal zones found under /sys/class/thermal".to_string(),
+            ));
+        }
+
+        Ok(readings)
+    }
+}
+
+impl DefaultThermalPluginImpl for LinuxThermalPlugin {
+    fn base(&self) -> &BasePlugin {
+        &self.base
+    }
+
+    fn is_supported(&self) -> bool {
+        is_thermal_available()
+    }
+}
+
+/// Enumerate thermal zone directories (`thermal_zone0`, `thermal_zone1`, ...).
+fn thermal_zone_dirs(root: &Path) -> Result<Vec<PathBuf>, HardwarePluginError> {
+    let entries = fs::read_dir(root).map_err(|e| {
+        HardwarePluginError::MeasurementError(format!("Failed to read {}: {}", root.display(), e))
+    })?;
+
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with("thermal_zone"))
+                .unwrap_or(false)
+        })
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Read a single zone's `type` and `temp` (millidegrees Celsius) and turn it into a
+/// `ThermalReading`.
+fn read_zone_reading(zone_dir: &Path) -> Result<ThermalReading, HardwarePluginError> {
+    let sensor = read_sysfs_string(&zone_dir.join("type"))?;
+    let millidegrees = read_sysfs_i64(&zone_dir.join("temp"))?;
+
+    Ok(ThermalReading {
+        timestamp: chrono::Utc::now(),
+        celsius: millidegrees as f64 / 1_000.0,
+        sensor,
+    })
+}
+
+fn read_sysfs_string(path: &Path) -> Result<String, HardwarePluginError> {
+    fs::read_to_string(path)
+        .map(|s| s.trim().to_string())
+        .map_err(|e| sysfs_read_error(path, e))
+}
+
+fn read_sysfs_i64(path: &Path) -> Result<i64, HardwarePluginError> {
+    let raw = read_sysfs_string(path)?;
+    raw.parse::<i64>().map_err(|e| {
+        HardwarePluginError::MeasurementError(format!(
+            "Failed to parse {} as i64: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn sysfs_read_error(path: &Path, err: std::io::Error) -> HardwarePluginError {
+    if err.kind() == std::io::ErrorKind::PermissionDenied {
+        HardwarePluginError::PermissionDenied(format!(
+            "Permission denied reading {}",
+            path.display()
+        ))
+    } else {
+        HardwarePluginError::MeasurementError(format!("Failed to read {}: {}", path.display(), err))
+    }
+}
+
+/// Check if any Linux thermal zone is available on the system.
+///
+/// Returns true only if the thermal sysfs root exists and at least one zone's `temp`
+/// file is actually readable.
+pub fn is_thermal_available() -> bool {
+    let root = Path::new(THERMAL_ROOT);
+    if !root.exists() {
+        return false;
+    }
+
+    match thermal_zone_dirs(root) {
+        Ok(dirs) => dirs
+            .iter()
+            .any(|dir| fs::read_to_string(dir.join("temp")).is_ok()),
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,268 @@
+//! Per-function energy attribution: a stack-based self-profiler that correlates the
+//! function spans `Instrumenter::collect_function_edits` finds (via
+//! `LanguageAdapter::get_function_query`) with the energy deltas read around the
+//! `__energy_probe_start`/`__energy_probe_end` hooks it wraps them in.
+//!
+//! `hardware::EnergyMonitor` only ever reports one whole-run total; `EnergyAttributor`
+//! instead maintains a call stack of `(function_id, entry_joules, children_joules)`
+//! frames so each invocation can be broken into *total* energy (its own span) and
+//! *self* energy (its span minus whatever its callees already accounted for), the same
+//! distinction a CPU interval profiler draws between cumulative and self time.
+
+use std::collections::HashMap;
+
+/// Identifies the source location a `function_id` was probed at, so an attributed
+/// hotspot can point back at real code instead of just a name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSpan {
+    pub function_id: String,
+    pub file_path: String,
+    pub line_start: usize,
+    pub line_end: usize,
+}
+
+/// Aggregated energy attribution for one function, across every invocation recorded by
+/// an `EnergyAttributor`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodeEnergyStats {
+    /// Sum of `exit_joules - entry_joules` across every *outermost* call (a recursive
+    /// function's nested calls already overlap their parent's span, so only counting
+    /// the outermost one keeps this from inflating past the wall-clock energy actually
+    /// spent). `None` if the energy source was unavailable for any contributing call —
+    /// a missing RAPL delta means the total is unknown, not zero.
+    pub total_joules: Option<f64>,
+    /// `total_joules` minus whatever energy this function's own callees accounted for,
+    /// summed across every invocation (self energy doesn't overlap across recursion
+    /// levels the way total does, so every call contributes). Same `None` caveat.
+    pub self_joules: Option<f64>,
+    pub calls: u64,
+}
+
+/// One active invocation on the attributor's call stack.
+struct Frame {
+    function_id: String,
+    entry_joules: Option<f64>,
+    /// Running total of `total_joules` already attributed to this frame's children.
+    /// `None` once any child's own total came back unknown, since a sum with a missing
+    /// term can't be trusted either.
+    children_joules: Option<f64>,
+}
+
+/// Drives the call-stack bookkeeping described in the module doc for one logical call
+/// stack — e.g. one instrumented run on one thread. A multi-threaded caller needs one
+/// attributor per thread, the same way a real interval profiler samples per-thread.
+#[derive(Default)]
+pub struct EnergyAttributor {
+    stack: Vec<Frame>,
+    /// Number of currently-active (not yet exited) calls per `function_id`, so a
+    /// recursive chain only counts its outermost call toward `total_joules`.
+    active_calls: HashMap<String, u32>,
+    /// Number of outermost calls seen so far per `function_id`, used the same way
+    /// `CodeEnergyStats::calls` is to decide whether `fold` is starting a fresh sum.
+    outermost_calls: HashMap<String, u64>,
+    spans: HashMap<String, FunctionSpan>,
+    stats: HashMap<String, CodeEnergyStats>,
+}
+
+impl EnergyAttributor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record entry into `span.function_id`, snapshotting `entry_joules` (the energy
+    /// reading taken alongside the `__energy_probe_start` hook, or `None` if the
+    /// energy source didn't produce one).
+    pub fn on_enter(&mut self, span: FunctionSpan, entry_joules: Option<f64>) {
+        *self.active_calls.entry(span.function_id.clone()).or_insert(0) += 1;
+        self.spans.insert(span.function_id.clone(), span.clone());
+        self.stack.push(Frame {
+            function_id: span.function_id,
+            entry_joules,
+            children_joules: Some(0.0),
+        });
+    }
+
+    /// Record exit from the innermost active call, snapshotting `exit_joules` (the
+    /// reading taken alongside the matching `__energy_probe_end` hook). Returns the
+    /// running `CodeEnergyStats` for the function that just exited.
+    ///
+    /// # Panics
+    /// Panics if called without a matching `on_enter` — every `__energy_probe_end`
+    /// hook is paired with exactly one `__energy_probe_start`, so an unbalanced call
+    /// means the instrumentation itself is broken.
+    pub fn on_exit(&mut self, exit_joules: Option<f64>) -> CodeEnergyStats {
+        let frame = self
+            .stack
+            .pop()
+            .expect("on_exit called without a matching on_enter");
+
+        let depth = self
+            .active_calls
+            .get_mut(&frame.function_id)
+            .expect("active_calls missing an entry for a frame still on the stack");
+        *depth -= 1;
+        let is_outermost = *depth == 0;
+        if is_outermost {
+            self.active_calls.remove(&frame.function_id);
+        }
+
+        let total = match (frame.entry_joules, exit_joules) {
+            (Some(entry), Some(exit)) => Some(exit - entry),
+            _ => None,
+        };
+        let self_energy = match (total, frame.children_joules) {
+            (Some(total), Some(children)) => Some(total - children),
+            _ => None,
+        };
+
+        // Fold this call's total into its parent's running children total, so the
+        // parent can later subtract it back out when computing its own self energy.
+        if let Some(parent) = self.stack.last_mut() {
+            parent.children_joules = match (parent.children_joules, total) {
+                (Some(children), Some(total)) => Some(children + total),
+                _ => None,
+            };
+        }
+
+        let entry = self.stats.entry(frame.function_id.clone()).or_default();
+        let self_is_first = entry.calls == 0;
+        entry.calls += 1;
+        entry.self_joules = Self::fold(entry.self_joules, self_energy, self_is_first);
+
+        if is_outermost {
+            let outermost_count = self
+                .outermost_calls
+                .entry(frame.function_id.clone())
+                .or_insert(0);
+            let total_is_first = *outermost_count == 0;
+            *outermost_count += 1;
+            entry.total_joules = Self::fold(entry.total_joules, total, total_is_first);
+        }
+
+        entry.clone()
+    }
+
+    /// Fold a new sample into a running sum: the first sample for a function is taken
+    /// as-is (so a function whose very first call had no energy data correctly starts
+    /// `None` rather than `Some(0.0)`), and afterwards a missing sample poisons the
+    /// whole sum rather than silently treating it as zero.
+    fn fold(existing: Option<f64>, new: Option<f64>, is_first: bool) -> Option<f64> {
+        if is_first {
+            return new;
+        }
+        match (existing, new) {
+            (Some(existing), Some(new)) => Some(existing + new),
+            _ => None,
+        }
+    }
+
+    /// Aggregated stats recorded so far, keyed by `function_id`.
+    pub fn stats(&self) -> &HashMap<String, CodeEnergyStats> {
+        &self.stats
+    }
+
+    /// The `function_id`s with the highest `self_joules`, paired with their recorded
+    /// span and stats, highest first. Functions with `self_joules: None` (no usable
+    /// energy data) sort after every function with a known value, rather than being
+    /// mistaken for a self energy of zero.
+    pub fn top_self_energy(&self, limit: usize) -> Vec<(FunctionSpan, CodeEnergyStats)> {
+        let mut ranked: Vec<(FunctionSpan, CodeEnergyStats)> = self
+            .stats
+            .iter()
+            .filter_map(|(function_id, stats)| {
+                self.spans
+                    .get(function_id)
+                    .map(|span| (span.clone(), stats.clone()))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| match (a.1.self_joules, b.1.self_joules) {
+            (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(std::cmp::Ordering::Equal),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        ranked.truncate(limit);
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(function_id: &str) -> FunctionSpan {
+        FunctionSpan {
+            function_id: function_id.to_string(),
+            file_path: "example.py".to_string(),
+            line_start: 1,
+            line_end: 2,
+        }
+    }
+
+    #[test]
+    fn leaf_call_attributes_all_energy_to_self() {
+        let mut attributor = EnergyAttributor::new();
+        attributor.on_enter(span("leaf"), Some(10.0));
+        let stats = attributor.on_exit(Some(14.0));
+
+        assert_eq!(stats.calls, 1);
+        assert_eq!(stats.total_joules, Some(4.0));
+        assert_eq!(stats.self_joules, Some(4.0));
+    }
+
+    #[test]
+    fn parent_self_energy_excludes_child_total() {
+        let mut attributor = EnergyAttributor::new();
+        attributor.on_enter(span("parent"), Some(0.0));
+        attributor.on_enter(span("child"), Some(1.0));
+        let child = attributor.on_exit(Some(3.0)); // child total = 2.0
+        let parent = attributor.on_exit(Some(10.0)); // parent total = 10.0
+
+        assert_eq!(child.self_joules, Some(2.0));
+        assert_eq!(parent.total_joules, Some(10.0));
+        assert_eq!(parent.self_joules, Some(8.0));
+    }
+
+    #[test]
+    fn recursive_calls_count_total_once_but_self_every_time() {
+        let mut attributor = EnergyAttributor::new();
+        attributor.on_enter(span("fib"), Some(0.0));
+        attributor.on_enter(span("fib"), Some(1.0));
+        let inner = attributor.on_exit(Some(2.0)); // inner total/self = 1.0
+        let outer = attributor.on_exit(Some(5.0)); // outer total = 5.0, self = 4.0
+
+        assert_eq!(inner.calls, 1);
+        assert_eq!(outer.calls, 2);
+        assert_eq!(outer.self_joules, Some(1.0 + 4.0));
+        // Only the outermost call's total counts, so it isn't inflated by the nested
+        // call's overlapping span.
+        assert_eq!(outer.total_joules, Some(5.0));
+    }
+
+    #[test]
+    fn missing_energy_reading_marks_stats_none_instead_of_zero() {
+        let mut attributor = EnergyAttributor::new();
+        attributor.on_enter(span("unsupported"), None);
+        let stats = attributor.on_exit(None);
+
+        assert_eq!(stats.total_joules, None);
+        assert_eq!(stats.self_joules, None);
+        assert_eq!(stats.calls, 1);
+    }
+
+    #[test]
+    fn top_self_energy_ranks_known_values_before_unknown() {
+        let mut attributor = EnergyAttributor::new();
+        attributor.on_enter(span("hot"), Some(0.0));
+        attributor.on_exit(Some(9.0));
+        attributor.on_enter(span("cold"), Some(0.0));
+        attributor.on_exit(Some(1.0));
+        attributor.on_enter(span("unknown"), None);
+        attributor.on_exit(None);
+
+        let ranked = attributor.top_self_energy(3);
+        let ids: Vec<&str> = ranked.iter().map(|(span, _)| span.function_id.as_str()).collect();
+        assert_eq!(ids, vec!["hot", "cold", "unknown"]);
+    }
+}
@@ -25,6 +25,8 @@ pub enum InstrumentationError {
     ParserError(String),
     #[error("Anyhow error: {0}")]
     AnyhowError(#[from] anyhow::Error),
+    #[error("Configuration error: {0}")]
+    ConfigError(String),
 }
 
 impl From<String> for InstrumentationError {
@@ -6,6 +6,20 @@ use futures_util::future::TryFutureExt;
 use hardware_plugins::{HardwarePlugin, Measurement};
 use crate::error::InstrumentationError;
 
+/// Compute the energy delta between two measurements, accounting for a fixed-width
+/// hardware counter wrapping around during the session (e.g. a RAPL `energy_uj`
+/// register reporting its range via `max_joules`). Without a known range the raw
+/// (possibly negative) delta is returned, since the source isn't known to wrap.
+fn energy_delta(start: &Measurement, end: &Measurement) -> f64 {
+    if end.joules >= start.joules {
+        return end.joules - start.joules;
+    }
+    match start.max_joules.or(end.max_joules) {
+        Some(max_joules) => (max_joules - start.joules) + end.joules,
+        None => end.joules - start.joules,
+    }
+}
+
 /// Async wrapper around hardware plugins
 pub struct AsyncHardwarePlugin {
     plugin: Arc<Mutex<Box<dyn HardwarePlugin>>>,
@@ -74,7 +88,9 @@ impl EnergyMonitor {
                 let start = &self.start_measurements[i];
                 end_measurements.push(Measurement {
                     timestamp: end.timestamp,
-                    joules: end.joules - start.joules,
+                    joules: energy_delta(start, &end),
+                    source: end.source.clone(),
+                    max_joules: None,
                 });
             }
         }
@@ -3,19 +3,24 @@
 //! This package provides tools for instrumenting code to measure energy consumption
 //! across different programming languages.
 
+pub mod attribution;
 mod error;
 mod instrumenter;
 mod parser;
+pub mod profile;
 
+pub use attribution::{CodeEnergyStats, EnergyAttributor, FunctionSpan};
 pub use error::InstrumentationError;
-pub use instrumenter::Instrumenter;
+pub use instrumenter::{Instrumenter, InstrumentedSource};
 pub use parser::Parser;
+pub use profile::{ConfigProfile, ConfigVariant, ProfileStore};
 
 /// Result type for instrumentation operations
 pub type Result<T> = std::result::Result<T, InstrumentationError>;
 
 /// Supported programming languages
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Language {
     Python,
     JavaScript,
@@ -33,8 +38,35 @@ impl Language {
     }
 }
 
+/// Selects which functions `Instrumenter::instrument_code` probes, by name.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FunctionFilter {
+    /// Instrument every function found.
+    All,
+    /// Only instrument functions whose name is in this list.
+    Allow(Vec<String>),
+    /// Instrument every function except those in this list.
+    Deny(Vec<String>),
+}
+
+impl FunctionFilter {
+    fn allows(&self, name: &str) -> bool {
+        match self {
+            FunctionFilter::All => true,
+            FunctionFilter::Allow(names) => names.iter().any(|n| n == name),
+            FunctionFilter::Deny(names) => !names.iter().any(|n| n == name),
+        }
+    }
+}
+
+impl Default for FunctionFilter {
+    fn default() -> Self {
+        FunctionFilter::All
+    }
+}
+
 /// Configuration for instrumentation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InstrumentationConfig {
     /// The language to instrument
     pub language: Language,
@@ -44,6 +76,8 @@ pub struct InstrumentationConfig {
     pub instrument_loops: bool,
     /// Whether to instrument memory operations
     pub instrument_memory: bool,
+    /// Allow/deny filter controlling which functions get probed
+    pub function_filter: FunctionFilter,
 }
 
 impl Default for InstrumentationConfig {
@@ -53,6 +87,7 @@ impl Default for InstrumentationConfig {
             instrument_functions: true,
             instrument_loops: true,
             instrument_memory: true,
+            function_filter: FunctionFilter::default(),
         }
     }
 }
@@ -77,6 +112,16 @@ pub trait LanguageAdapter {
     fn get_function_query(&self) -> &str;
     fn get_class_query(&self) -> &str;
     fn get_import_query(&self) -> &str;
+    /// Matches loop constructs (`for`/`while`) whose body should be wrapped with
+    /// before/after measurement hooks, the same way `get_function_query` does for
+    /// function bodies. Every match must capture the loop's body block as `@loop.body`.
+    fn get_loop_query(&self) -> &str;
+    /// Matches heap-allocating expressions (list/dict/set literals and comprehensions)
+    /// whose value should be wrapped with a measurement probe, captured as
+    /// `@memory.alloc`. Unlike `get_function_query`/`get_loop_query`, these are
+    /// expression nodes rather than statement blocks, so `Instrumenter::instrument_code`
+    /// wraps the expression in a probe call instead of inserting hooks around a body.
+    fn get_memory_query(&self) -> &str;
 }
 
 pub struct PythonAdapter {
@@ -129,6 +174,28 @@ impl LanguageAdapter for PythonAdapter {
         (import_from_statement) @import.from
         "#
     }
+
+    fn get_loop_query(&self) -> &str {
+        r#"
+        (for_statement
+            body: (block) @loop.body
+        )
+        (while_statement
+            body: (block) @loop.body
+        )
+        "#
+    }
+
+    fn get_memory_query(&self) -> &str {
+        r#"
+        (list) @memory.alloc
+        (dictionary) @memory.alloc
+        (set) @memory.alloc
+        (list_comprehension) @memory.alloc
+        (dictionary_comprehension) @memory.alloc
+        (set_comprehension) @memory.alloc
+        "#
+    }
 }
 
 pub struct InstrumentationEngine {
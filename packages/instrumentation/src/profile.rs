@@ -0,0 +1,156 @@
+//! Named `InstrumentationConfig` profiles, each holding several named variants,
+//! following the profile/variant model used by PowerTools. Lets a project check in a
+//! shared measurement configuration (e.g. `ci-fast`, `deep-per-function`) and switch
+//! granularity via `Instrumenter::load_profile` instead of touching code.
+
+use crate::{InstrumentationConfig, InstrumentationError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One named configuration within a profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigVariant {
+    pub name: String,
+    pub config: InstrumentationConfig,
+}
+
+/// A named group of variants, e.g. a project's `"ci"` profile might hold `"fast"` and
+/// `"thorough"` variants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigProfile {
+    pub id: String,
+    /// Variant used by `variant(None)` when the caller doesn't name one explicitly.
+    pub default_variant: Option<String>,
+    pub variants: Vec<ConfigVariant>,
+}
+
+impl ConfigProfile {
+    /// Resolve a variant by name, falling back to `default_variant`, falling back to
+    /// the first declared variant if neither is set.
+    pub fn variant(&self, name: Option<&str>) -> Result<&ConfigVariant> {
+        let wanted = name.or(self.default_variant.as_deref());
+
+        if let Some(wanted) = wanted {
+            return self
+                .variants
+                .iter()
+                .find(|v| v.name == wanted)
+                .ok_or_else(|| {
+                    InstrumentationError::ConfigError(format!(
+                        "profile '{}' has no variant named '{}'",
+                        self.id, wanted
+                    ))
+                });
+        }
+
+        self.variants.first().ok_or_else(|| {
+            InstrumentationError::ConfigError(format!("profile '{}' has no variants", self.id))
+        })
+    }
+}
+
+/// A collection of `ConfigProfile`s, persisted as TOML or JSON.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileStore {
+    pub profiles: Vec<ConfigProfile>,
+}
+
+impl ProfileStore {
+    /// Load a profile store from a TOML file.
+    pub fn load_toml(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        toml::from_str(&text).map_err(|e| InstrumentationError::ConfigError(e.to_string()))
+    }
+
+    /// Load a profile store from a JSON file.
+    pub fn load_json(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(|e| InstrumentationError::ConfigError(e.to_string()))
+    }
+
+    /// Persist the store as TOML.
+    pub fn save_toml(&self, path: &Path) -> Result<()> {
+        let text = toml::to_string_pretty(self)
+            .map_err(|e| InstrumentationError::ConfigError(e.to_string()))?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Persist the store as JSON.
+    pub fn save_json(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|e| InstrumentationError::ConfigError(e.to_string()))?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Look up a profile by id.
+    pub fn profile(&self, id: &str) -> Result<&ConfigProfile> {
+        self.profiles
+            .iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| InstrumentationError::ConfigError(format!("unknown profile '{}'", id)))
+    }
+
+    /// List every known profile id, for discovery.
+    pub fn profile_ids(&self) -> Vec<&str> {
+        self.profiles.iter().map(|p| p.id.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_store() -> ProfileStore {
+        ProfileStore {
+            profiles: vec![ConfigProfile {
+                id: "ci".to_string(),
+                default_variant: Some("fast".to_string()),
+                variants: vec![
+                    ConfigVariant {
+                        name: "fast".to_string(),
+                        config: InstrumentationConfig {
+                            instrument_loops: false,
+                            instrument_memory: false,
+                            ..InstrumentationConfig::default()
+                        },
+                    },
+                    ConfigVariant {
+                        name: "thorough".to_string(),
+                        config: InstrumentationConfig::default(),
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_default_variant_fallback() {
+        let store = sample_store();
+        let profile = store.profile("ci").unwrap();
+        let variant = profile.variant(None).unwrap();
+        assert_eq!(variant.name, "fast");
+    }
+
+    #[test]
+    fn test_named_variant_lookup() {
+        let store = sample_store();
+        let profile = store.profile("ci").unwrap();
+        let variant = profile.variant(Some("thorough")).unwrap();
+        assert!(variant.config.instrument_loops);
+    }
+
+    #[test]
+    fn test_unknown_variant_is_an_error() {
+        let store = sample_store();
+        let profile = store.profile("ci").unwrap();
+        assert!(profile.variant(Some("nonexistent")).is_err());
+    }
+
+    #[test]
+    fn test_unknown_profile_is_an_error() {
+        let store = sample_store();
+        assert!(store.profile("nonexistent").is_err());
+    }
+}
@@ -1,10 +1,65 @@
 //! C++ language adapter
 
+use tree_sitter::Language;
+use crate::hotspots::{analyze_hotspots, HotspotRule};
+use crate::LanguageAdapter;
 use crate::LanguageAdapterError;
 use crate::AnalysisResult;
-use crate::CodeHotspot;
 use crate::OptimizationSuggestion;
 
+extern "C" {
+    fn tree_sitter_cpp() -> Language;
+}
+
+const HOTSPOT_RULES: &[HotspotRule] = &[
+    HotspotRule {
+        name: "nested_loop",
+        query: r#"(for_statement body: (compound_statement (for_statement) @pattern.match))"#,
+        text_filter: None,
+        energy_impact: 3.0,
+        description: "nested loop allocates or re-runs the inner body's work on every outer iteration",
+        suggestion: "Hoist loop-invariant allocations out of the inner loop, or flatten the iteration into a single pass.",
+        code_snippet: "std::vector<T> cache;\ncache.reserve(outer.size());\nfor (auto& x : outer) cache.push_back(compute(x));\nfor (auto& value : cache) { ... }",
+        difficulty: "medium",
+    },
+    HotspotRule {
+        name: "string_concat_in_loop",
+        query: r#"(for_statement body: (compound_statement (expression_statement (assignment_expression) @pattern.match)))"#,
+        text_filter: None,
+        energy_impact: 2.0,
+        description: "value assigned or appended to inside a loop (the operand's type is not checked); a std::string grown this way re-allocates as it outgrows its capacity",
+        suggestion: "Reserve capacity up front and append into one buffer instead of reassigning/concatenating in place.",
+        code_snippet: "std::string out;\nout.reserve(estimate);\nfor (auto& value : items) out += value;",
+        difficulty: "easy",
+    },
+    HotspotRule {
+        name: "busy_wait",
+        query: r#"(while_statement) @pattern.match"#,
+        text_filter: Some(|text| {
+            let lower = text.to_lowercase();
+            !lower.contains("sleep") && !lower.contains("wait") && !lower.contains("poll(")
+        }),
+        energy_impact: 4.0,
+        description: "while loop polls without yielding or sleeping, spinning the CPU",
+        suggestion: "Add a short sleep between polls, or block on a condition variable instead of spinning.",
+        code_snippet: "while (!ready()) {\n    std::this_thread::sleep_for(std::chrono::milliseconds(10));\n}",
+        difficulty: "easy",
+    },
+    HotspotRule {
+        name: "sync_io_in_loop",
+        query: r#"(for_statement body: (compound_statement (expression_statement (call_expression) @pattern.match)))"#,
+        text_filter: Some(|text| {
+            const BLOCKING_CALLS: &[&str] = &["read(", "write(", "fopen(", "recv(", "fread(", "fwrite("];
+            BLOCKING_CALLS.iter().any(|needle| text.contains(needle))
+        }),
+        energy_impact: 5.0,
+        description: "blocking I/O call executed on every loop iteration",
+        suggestion: "Move the I/O outside the loop, or switch to a buffered/async API so the loop doesn't block on each call.",
+        code_snippet: "std::string data = read_all(path);\nfor (auto& line : split_lines(data)) { ... }",
+        difficulty: "hard",
+    },
+];
+
 /// C++ language adapter
 pub struct CppAdapter {
     parser: Option<tree_sitter::Parser>,
@@ -13,26 +68,55 @@ pub struct CppAdapter {
 impl CppAdapter {
     /// Create a new C++ adapter
     pub fn new() -> Result<Self, LanguageAdapterError> {
-        // TODO: Initialize C++ parser
         Ok(Self {
             parser: None,
         })
     }
 
-    /// Analyze C++ code for energy consumption
+    /// Analyze C++ code for energy hotspots using the shared tree-sitter engine.
     pub fn analyze_code(&self, code: &str) -> Result<AnalysisResult, LanguageAdapterError> {
-        // TODO: Implement C++ code analysis
-        Ok(AnalysisResult {
-            language: "cpp".to_string(),
-            energy_score: 0.0,
-            hotspots: Vec::new(),
-            suggestions: Vec::new(),
-        })
+        Ok(analyze_hotspots(self, code))
     }
 
     /// Get C++-specific optimization suggestions
     pub fn get_suggestions(&self, code: &str) -> Result<Vec<OptimizationSuggestion>, LanguageAdapterError> {
-        // TODO: Implement C++-specific suggestions
-        Ok(Vec::new())
+        Ok(self.analyze_code(code)?.suggestions)
+    }
+}
+
+impl LanguageAdapter for CppAdapter {
+    fn get_language_id(&self) -> &'static str {
+        "cpp"
+    }
+
+    fn get_grammar(&self) -> Language {
+        unsafe { tree_sitter_cpp() }
     }
-} 
\ No newline at end of file
+
+    fn get_function_query(&self) -> &'static str {
+        r#"
+        (function_definition
+            declarator: (function_declarator
+                declarator: (identifier) @function.name)
+            body: (compound_statement) @function.body
+        )
+        "#
+    }
+
+    fn get_class_query(&self) -> &'static str {
+        r#"
+        (class_specifier
+            name: (type_identifier) @class.name
+            body: (field_declaration_list) @class.body
+        )
+        "#
+    }
+
+    fn get_import_query(&self) -> &'static str {
+        r#"(preproc_include) @import"#
+    }
+
+    fn get_hotspot_rules(&self) -> &'static [HotspotRule] {
+        HOTSPOT_RULES
+    }
+}
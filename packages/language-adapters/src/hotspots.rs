@@ -0,0 +1,92 @@
+//! Shared, per-language energy hotspot detection engine.
+//!
+//! Each `LanguageAdapter` declares its own `HotspotRule` set — tree-sitter queries
+//! tailored to its grammar's node kinds — and `analyze_hotspots` runs every rule
+//! against the parsed source, turning matches into `CodeHotspot`/`OptimizationSuggestion`
+//! pairs. This lets the same engine drive Python, Rust, and C++ (and any future
+//! adapter) instead of each language reimplementing its own static analysis pass.
+
+use crate::{AnalysisResult, CodeHotspot, LanguageAdapter, OptimizationSuggestion};
+use tree_sitter::{Query, QueryCursor};
+
+/// The capture every rule's query must tag its hotspot node with.
+const CAPTURE_NAME: &str = "pattern.match";
+
+/// One energy-costly pattern to search for in a language's syntax tree.
+pub struct HotspotRule {
+    /// Stable identifier for the pattern, prefixed onto the hotspot description.
+    pub name: &'static str,
+    /// Tree-sitter query; the node tagged `@pattern.match` is reported as the hotspot span.
+    pub query: &'static str,
+    /// Extra filter over the matched node's source text, for patterns a query alone
+    /// can't express (e.g. "loop body never calls sleep", "callee is a known blocking
+    /// I/O function"). `None` means every structural match counts.
+    pub text_filter: Option<fn(&str) -> bool>,
+    /// Weight contributed to `AnalysisResult::energy_score` per match.
+    pub energy_impact: f64,
+    pub description: &'static str,
+    pub suggestion: &'static str,
+    pub code_snippet: &'static str,
+    pub difficulty: &'static str,
+}
+
+/// Run every one of `adapter`'s hotspot rules against `source` and collect the
+/// resulting hotspots, suggestions, and aggregate energy score.
+pub fn analyze_hotspots(adapter: &dyn LanguageAdapter, source: &str) -> AnalysisResult {
+    let tree = adapter.parse(source);
+    let mut hotspots = Vec::new();
+    let mut suggestions = Vec::new();
+    let mut energy_score = 0.0;
+
+    for rule in adapter.get_hotspot_rules() {
+        let query = match Query::new(adapter.get_grammar(), rule.query) {
+            Ok(q) => q,
+            Err(_) => continue, // rule's query doesn't apply to this grammar; skip it
+        };
+        let capture_index = match query.capture_index_for_name(CAPTURE_NAME) {
+            Some(idx) => idx,
+            None => continue,
+        };
+
+        let mut cursor = QueryCursor::new();
+        for m in cursor.matches(&query, tree.root_node(), source.as_bytes()) {
+            let capture = match m.captures.iter().find(|c| c.index == capture_index) {
+                Some(c) => c,
+                None => continue,
+            };
+            let node = capture.node;
+            let text = node.utf8_text(source.as_bytes()).unwrap_or("");
+
+            if let Some(filter) = rule.text_filter {
+                if !filter(text) {
+                    continue;
+                }
+            }
+
+            let start = node.start_position();
+            let end = node.end_position();
+
+            hotspots.push(CodeHotspot {
+                file_path: String::new(),
+                line_start: start.row + 1,
+                line_end: end.row + 1,
+                energy_impact: rule.energy_impact,
+                description: format!("{}: {}", rule.name, rule.description),
+            });
+            suggestions.push(OptimizationSuggestion {
+                description: rule.suggestion.to_string(),
+                impact: rule.energy_impact,
+                difficulty: rule.difficulty.to_string(),
+                code_snippet: rule.code_snippet.to_string(),
+            });
+            energy_score += rule.energy_impact;
+        }
+    }
+
+    AnalysisResult {
+        language: adapter.get_language_id().to_string(),
+        energy_score,
+        hotspots,
+        suggestions,
+    }
+}
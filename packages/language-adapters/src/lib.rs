@@ -7,6 +7,7 @@ pub mod python;
 pub mod rust;
 pub mod cpp;
 pub mod common;
+pub mod hotspots;
 
 use thiserror::Error;
 
@@ -88,4 +89,11 @@ pub trait LanguageAdapter {
 
     /// Get the query for finding import/require statements in this language
     fn get_import_query(&self) -> &'static str;
+
+    /// Energy hotspot rules for this language, run by `hotspots::analyze_hotspots`.
+    /// Defaults to no rules so adapters that don't have an analysis pass yet don't
+    /// have to stub this out.
+    fn get_hotspot_rules(&self) -> &'static [hotspots::HotspotRule] {
+        &[]
+    }
 } 
\ No newline at end of file
@@ -1,10 +1,68 @@
 use tree_sitter::Language;
-use crate::LanguageAdapter;
+use crate::hotspots::{analyze_hotspots, HotspotRule};
+use crate::{AnalysisResult, LanguageAdapter, LanguageAdapterError, OptimizationSuggestion};
 
 extern "C" {
     fn tree_sitter_python() -> Language;
 }
 
+const HOTSPOT_RULES: &[HotspotRule] = &[
+    HotspotRule {
+        name: "nested_loop",
+        query: r#"(for_statement body: (block (for_statement) @pattern.match))"#,
+        text_filter: None,
+        energy_impact: 3.0,
+        description: "nested loop re-runs the inner body's work on every outer iteration",
+        suggestion: "Hoist loop-invariant allocations or computation out of the inner loop, or flatten the iteration into a single pass.",
+        code_snippet: "cache = [compute(x) for x in outer]\nfor value in cache:\n    ...",
+        difficulty: "medium",
+    },
+    HotspotRule {
+        name: "string_concat_in_loop",
+        query: r#"(for_statement body: (block (augmented_assignment) @pattern.match))"#,
+        text_filter: Some(|text| text.contains("+=")),
+        energy_impact: 2.0,
+        description: "value built up with += inside a loop, re-allocating on every iteration",
+        suggestion: "Collect the pieces in a list and join once after the loop instead of concatenating in place.",
+        code_snippet: "parts = []\nfor value in items:\n    parts.append(str(value))\nresult = \"\".join(parts)",
+        difficulty: "easy",
+    },
+    HotspotRule {
+        name: "busy_wait",
+        query: r#"(while_statement) @pattern.match"#,
+        text_filter: Some(|text| {
+            let lower = text.to_lowercase();
+            !lower.contains("sleep") && !lower.contains("wait") && !lower.contains("select(")
+        }),
+        energy_impact: 4.0,
+        description: "while loop polls without yielding or sleeping, spinning the CPU",
+        suggestion: "Add a short time.sleep(...) between polls, or block on a condition variable/event instead of spinning.",
+        code_snippet: "while not ready():\n    time.sleep(0.01)",
+        difficulty: "easy",
+    },
+    HotspotRule {
+        name: "sync_io_in_loop",
+        query: r#"(for_statement body: (block (expression_statement (call) @pattern.match)))"#,
+        text_filter: Some(|text| {
+            const BLOCKING_CALLS: &[&str] = &[
+                "open(",
+                "read(",
+                "write(",
+                "recv(",
+                "requests.get(",
+                "requests.post(",
+                "urlopen(",
+            ];
+            BLOCKING_CALLS.iter().any(|needle| text.contains(needle))
+        }),
+        energy_impact: 5.0,
+        description: "blocking I/O call executed on every loop iteration",
+        suggestion: "Batch the I/O outside the loop, or switch to a buffered/async API so the loop doesn't block on each call.",
+        code_snippet: "with open(path) as f:\n    data = f.read()\nfor line in data.splitlines():\n    ...",
+        difficulty: "hard",
+    },
+];
+
 /// Python language adapter implementation
 pub struct PythonAdapter;
 
@@ -12,6 +70,16 @@ impl PythonAdapter {
     pub fn new() -> Self {
         PythonAdapter
     }
+
+    /// Analyze Python code for energy hotspots using the shared tree-sitter engine.
+    pub fn analyze_code(&self, code: &str) -> Result<AnalysisResult, LanguageAdapterError> {
+        Ok(analyze_hotspots(self, code))
+    }
+
+    /// Get Python-specific optimization suggestions.
+    pub fn get_suggestions(&self, code: &str) -> Result<Vec<OptimizationSuggestion>, LanguageAdapterError> {
+        Ok(self.analyze_code(code)?.suggestions)
+    }
 }
 
 impl LanguageAdapter for PythonAdapter {
@@ -48,4 +116,8 @@ impl LanguageAdapter for PythonAdapter {
         (import_from_statement) @import.from
         "#
     }
-} 
\ No newline at end of file
+
+    fn get_hotspot_rules(&self) -> &'static [HotspotRule] {
+        HOTSPOT_RULES
+    }
+}
@@ -1,16 +1,72 @@
 //! Rust language adapter
 
 use tree_sitter::Language;
+use crate::hotspots::{analyze_hotspots, HotspotRule};
 use crate::LanguageAdapter;
 use crate::LanguageAdapterError;
 use crate::AnalysisResult;
-use crate::CodeHotspot;
 use crate::OptimizationSuggestion;
 
 extern "C" {
     fn tree_sitter_rust() -> Language;
 }
 
+const HOTSPOT_RULES: &[HotspotRule] = &[
+    HotspotRule {
+        name: "nested_loop",
+        query: r#"(for_expression body: (block (for_expression) @pattern.match))"#,
+        text_filter: None,
+        energy_impact: 3.0,
+        description: "nested loop re-runs the inner body's work on every outer iteration",
+        suggestion: "Hoist loop-invariant allocations out of the inner loop, or flatten the iteration into a single pass.",
+        code_snippet: "let cache: Vec<_> = outer.iter().map(compute).collect();\nfor value in &cache {\n    ...\n}",
+        difficulty: "medium",
+    },
+    HotspotRule {
+        name: "string_concat_in_loop",
+        query: r#"(for_expression body: (block (expression_statement (compound_assignment_expr) @pattern.match)))"#,
+        text_filter: Some(|text| text.contains("+=")),
+        energy_impact: 2.0,
+        description: "value built up with += inside a loop (the operand's type is not checked); a String grown this way re-allocates as it outgrows its capacity",
+        suggestion: "Reserve capacity up front and push_str into one buffer, or collect into a Vec and join once.",
+        code_snippet: "let mut out = String::with_capacity(estimate);\nfor value in items {\n    out.push_str(&value);\n}",
+        difficulty: "easy",
+    },
+    HotspotRule {
+        name: "busy_wait",
+        query: r#"(while_expression) @pattern.match"#,
+        text_filter: Some(|text| {
+            let lower = text.to_lowercase();
+            !lower.contains("sleep") && !lower.contains("wait") && !lower.contains("park")
+        }),
+        energy_impact: 4.0,
+        description: "while loop polls without yielding or sleeping, spinning the CPU",
+        suggestion: "Add a short thread::sleep(...) between polls, or block on a condvar/channel instead of spinning.",
+        code_snippet: "while !ready() {\n    std::thread::sleep(Duration::from_millis(10));\n}",
+        difficulty: "easy",
+    },
+    HotspotRule {
+        name: "sync_io_in_loop",
+        query: r#"(for_expression body: (block (expression_statement (call_expression) @pattern.match)))"#,
+        text_filter: Some(|text| {
+            const BLOCKING_CALLS: &[&str] = &[
+                "File::open",
+                "fs::read",
+                "std::io::stdin",
+                ".read(",
+                ".write(",
+                "TcpStream::connect",
+            ];
+            BLOCKING_CALLS.iter().any(|needle| text.contains(needle))
+        }),
+        energy_impact: 5.0,
+        description: "blocking I/O call executed on every loop iteration",
+        suggestion: "Move the I/O outside the loop, or switch to a buffered/async API so the loop doesn't block on each call.",
+        code_snippet: "let data = fs::read(path)?;\nfor line in data.lines() {\n    ...\n}",
+        difficulty: "hard",
+    },
+];
+
 /// Rust language adapter
 pub struct RustAdapter {
     parser: Option<tree_sitter::Parser>,
@@ -19,27 +75,19 @@ pub struct RustAdapter {
 impl RustAdapter {
     /// Create a new Rust adapter
     pub fn new() -> Result<Self, LanguageAdapterError> {
-        // TODO: Initialize Rust parser
         Ok(Self {
             parser: None,
         })
     }
 
-    /// Analyze Rust code for energy consumption
+    /// Analyze Rust code for energy hotspots using the shared tree-sitter engine.
     pub fn analyze_code(&self, code: &str) -> Result<AnalysisResult, LanguageAdapterError> {
-        // TODO: Implement Rust code analysis
-        Ok(AnalysisResult {
-            language: "rust".to_string(),
-            energy_score: 0.0,
-            hotspots: Vec::new(),
-            suggestions: Vec::new(),
-        })
+        Ok(analyze_hotspots(self, code))
     }
 
     /// Get Rust-specific optimization suggestions
     pub fn get_suggestions(&self, code: &str) -> Result<Vec<OptimizationSuggestion>, LanguageAdapterError> {
-        // TODO: Implement Rust-specific suggestions
-        Ok(Vec::new())
+        Ok(self.analyze_code(code)?.suggestions)
     }
 }
 
@@ -77,4 +125,8 @@ impl LanguageAdapter for RustAdapter {
         (extern_crate_declaration) @extern
         "#
     }
-} 
\ No newline at end of file
+
+    fn get_hotspot_rules(&self) -> &'static [HotspotRule] {
+        HOTSPOT_RULES
+    }
+}
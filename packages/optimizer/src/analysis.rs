@@ -1,6 +1,10 @@
 //! Code analysis for energy optimization
 
 use crate::OptimizerError;
+use instrumentation::EnergyAttributor;
+use language_adapters::cpp::CppAdapter;
+use language_adapters::python::PythonAdapter;
+use language_adapters::rust::RustAdapter;
 
 /// Represents the result of code analysis
 #[derive(Debug, Clone)]
@@ -18,20 +22,100 @@ pub struct CodeHotspot {
     pub line_end: usize,
     pub energy_impact: f64,
     pub description: String,
+    /// Self energy (in joules) attributed to this function by an `EnergyAttributor`
+    /// run, i.e. energy actually observed at runtime rather than `energy_impact`'s
+    /// static heuristic weight. `None` for hotspots surfaced by the static analyzer
+    /// (no profiling run backs them) or, for a profiled hotspot, if the energy source
+    /// was unavailable for that run rather than the self energy genuinely being zero.
+    pub self_energy_joules: Option<f64>,
 }
 
-/// Analyze code for energy consumption patterns
-pub fn analyze_energy_patterns(code: &str) -> Result<AnalysisResult, OptimizerError> {
-    // TODO: Implement energy pattern analysis
+/// Analyze code for energy consumption patterns, dispatching to the
+/// `language_adapters` tree-sitter hotspot engine for `language_id` and combining its
+/// result with this crate's own complexity metric.
+pub fn analyze_energy_patterns(
+    code: &str,
+    language_id: &str,
+) -> Result<AnalysisResult, OptimizerError> {
+    let result = match language_id {
+        "python" => PythonAdapter::new().analyze_code(code),
+        "rust" => RustAdapter::new()
+            .map_err(|e| OptimizerError::AnalysisError(e.to_string()))?
+            .analyze_code(code),
+        "cpp" => CppAdapter::new()
+            .map_err(|e| OptimizerError::AnalysisError(e.to_string()))?
+            .analyze_code(code),
+        other => {
+            return Err(OptimizerError::AnalysisError(format!(
+                "unsupported language: {}",
+                other
+            )))
+        }
+    }
+    .map_err(|e| OptimizerError::AnalysisError(e.to_string()))?;
+
     Ok(AnalysisResult {
-        energy_score: 0.0,
-        hotspots: Vec::new(),
-        complexity: 0.0,
+        energy_score: result.energy_score,
+        hotspots: result
+            .hotspots
+            .into_iter()
+            .map(|h| CodeHotspot {
+                file_path: h.file_path,
+                line_start: h.line_start,
+                line_end: h.line_end,
+                energy_impact: h.energy_impact,
+                description: h.description,
+                self_energy_joules: None,
+            })
+            .collect(),
+        complexity: calculate_complexity(code)?,
     })
 }
 
-/// Calculate code complexity metrics
+/// Turn an `EnergyAttributor`'s top self-energy functions into `CodeHotspot`s and
+/// append them to `result.hotspots`, alongside whatever the static `analyze_hotspots`
+/// engine already found. Unlike those static hotspots (weighted by a fixed heuristic),
+/// these describe energy actually observed during an instrumented run: `energy_impact`
+/// is left at `0.0` since it isn't the static heuristic weight these hotspots were
+/// never scored by, and `self_energy_joules` carries the real measurement instead
+/// (`None` if the run's energy source was unavailable, per `CodeEnergyStats`).
+pub fn merge_energy_attribution(result: &mut AnalysisResult, attributor: &EnergyAttributor, top_n: usize) {
+    for (span, stats) in attributor.top_self_energy(top_n) {
+        let description = match stats.self_joules {
+            Some(joules) => format!(
+                "{} spent {:.3}J of self energy across {} call(s)",
+                span.function_id, joules, stats.calls
+            ),
+            None => format!(
+                "{} was profiled across {} call(s) but no energy reading was available",
+                span.function_id, stats.calls
+            ),
+        };
+
+        result.hotspots.push(CodeHotspot {
+            file_path: span.file_path,
+            line_start: span.line_start,
+            line_end: span.line_end,
+            energy_impact: 0.0,
+            description,
+            self_energy_joules: stats.self_joules,
+        });
+    }
+}
+
+/// Calculate code complexity as a branch/loop density heuristic: each control-flow
+/// keyword adds to the score, weighted by its brace nesting depth at that point.
 pub fn calculate_complexity(code: &str) -> Result<f64, OptimizerError> {
-    // TODO: Implement complexity calculation
-    Ok(0.0)
-} 
\ No newline at end of file
+    const BRANCH_KEYWORDS: &[&str] = &["if ", "for ", "while ", "match ", "switch ", "case ", "catch "];
+    let mut depth: i32 = 0;
+    let mut complexity = 0.0;
+    for line in code.lines() {
+        let trimmed = line.trim_start();
+        if BRANCH_KEYWORDS.iter().any(|kw| trimmed.starts_with(kw)) {
+            complexity += 1.0 + depth as f64 * 0.5;
+        }
+        depth += line.matches('{').count() as i32;
+        depth -= line.matches('}').count() as i32;
+    }
+    Ok(complexity)
+}
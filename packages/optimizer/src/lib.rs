@@ -4,6 +4,8 @@
 //! consumption in code.
 
 pub mod analysis;
+#[cfg(feature = "scripting")]
+pub mod scripting;
 pub mod suggestions;
 pub mod metrics;
 
@@ -14,12 +16,15 @@ use thiserror::Error;
 pub enum OptimizerError {
     #[error("Failed to analyze code: {0}")]
     AnalysisError(String),
-    
+
     #[error("Failed to generate suggestions: {0}")]
     SuggestionError(String),
-    
+
     #[error("Failed to calculate metrics: {0}")]
     MetricsError(String),
+
+    #[error("Scripting error: {0}")]
+    ScriptingError(String),
 }
 
 /// Initialize the optimizer
@@ -28,10 +33,11 @@ pub fn init() -> Result<(), OptimizerError> {
     Ok(())
 }
 
-/// Analyze code for energy optimization opportunities
-pub fn analyze_code(code: &str) -> Result<Vec<OptimizationSuggestion>, OptimizerError> {
-    // TODO: Implement code analysis
-    Ok(Vec::new())
+/// Analyze code for energy optimization opportunities: runs the hotspot analysis for
+/// `language_id` and turns the result into a concrete suggestion per hotspot.
+pub fn analyze_code(code: &str, language_id: &str) -> Result<Vec<OptimizationSuggestion>, OptimizerError> {
+    let result = analysis::analyze_energy_patterns(code, language_id)?;
+    suggestions::generate_suggestions(code, &result)
 }
 
 /// Represents a suggestion for optimizing energy consumption
@@ -41,4 +47,18 @@ pub struct OptimizationSuggestion {
     pub impact: f64,
     pub difficulty: String,
     pub code_snippet: String,
+    /// Priority bucket, when the suggestion came with its own rather than needing
+    /// `suggestions::priority_for_impact` to derive one — e.g. a `scripting::RuleEngine`
+    /// rule that judged its own priority from more context than `impact` alone.
+    pub priority: Option<suggestions::SuggestionPriority>,
+    /// Estimated joule saving read back by `suggestions::calculate_savings`, when a
+    /// source more precise than the static `impact` heuristic provided one (again,
+    /// a `scripting::RuleEngine` rule's own estimate).
+    pub estimated_joules: Option<f64>,
+    /// 1-indexed start/end lines of the hotspot this suggestion addresses, read back by
+    /// `suggestions::apply_suggestion` to know which span of the original code to
+    /// replace with `code_snippet`. `None` for a suggestion with no known source
+    /// location to apply against.
+    pub line_start: Option<usize>,
+    pub line_end: Option<usize>,
 } 
\ No newline at end of file
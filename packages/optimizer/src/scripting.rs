@@ -0,0 +1,297 @@
+//! Lua-scriptable optimization rules, behind the optional `scripting` cargo feature.
+//!
+//! `suggestions::suggestion_for_hotspot`'s rule table is a fixed `match` over a
+//! hotspot's description prefix, so every new energy pattern means recompiling this
+//! crate. `RuleEngine` instead loads every `*.lua` file in a rules directory at
+//! startup and runs each one's `evaluate` function against the parsed function bodies
+//! and a hotspot from `AnalysisResult`, so a project can add language-specific rules
+//! (e.g. "replace busy-wait loop", "batch I/O") without touching Rust.
+//!
+//! Each rule script must define a global `evaluate(nodes, hotspot)`, called once per
+//! hotspot in an `AnalysisResult`:
+//! - `nodes`: every function body the adapter's `get_function_query` matched, as an
+//!   array of tables `{ name, start_line, end_line, text }`. Parsed once per
+//!   `generate_suggestions` call and passed to every rule, whether or not it reads
+//!   them. The `codegreen.query_functions(code, language_id)` host function returns
+//!   the same tables for any other code a rule wants to inspect.
+//! - `hotspot`: the hotspot being evaluated, as `{ file_path, line_start, line_end,
+//!   energy_impact, description }`.
+//!
+//! `evaluate` returns `nil` to decline, or a table describing a suggestion:
+//! `{ description, rationale, difficulty, code_snippet, priority, estimated_joules }`,
+//! where `priority` is one of `"high"`/`"medium"`/`"low"` (any other string reads as
+//! `"medium"`; an omitted `priority` leaves the suggestion's `priority` as `None`)
+//! and `estimated_joules` is the joule saving `suggestions::calculate_savings` reads
+//! back in preference to the static `impact` heuristic.
+
+use crate::analysis::CodeHotspot;
+use crate::suggestions::SuggestionPriority;
+use crate::{OptimizationSuggestion, OptimizerError};
+use language_adapters::cpp::CppAdapter;
+use language_adapters::python::PythonAdapter;
+use language_adapters::rust::RustAdapter;
+use language_adapters::LanguageAdapter;
+use mlua::{Lua, Table, Value};
+use std::fs;
+use std::path::Path;
+use tree_sitter::{Query, QueryCursor};
+
+/// One function body matched by an adapter's `get_function_query`, handed to Lua rules
+/// as a plain table rather than a live tree-sitter node (which can't cross the FFI
+/// boundary cheaply and would outlive the tree it was parsed from).
+struct FunctionNode {
+    name: String,
+    start_line: usize,
+    end_line: usize,
+    text: String,
+}
+
+fn lua_err(e: mlua::Error) -> OptimizerError {
+    OptimizerError::ScriptingError(e.to_string())
+}
+
+/// Look up the `LanguageAdapter` for `language_id`, the same dispatch
+/// `analysis::analyze_energy_patterns` uses.
+fn adapter_for(language_id: &str) -> Result<Box<dyn LanguageAdapter>, OptimizerError> {
+    match language_id {
+        "python" => Ok(Box::new(PythonAdapter::new())),
+        "rust" => Ok(Box::new(RustAdapter::new().map_err(|e| {
+            OptimizerError::ScriptingError(e.to_string())
+        })?)),
+        "cpp" => Ok(Box::new(CppAdapter::new().map_err(|e| {
+            OptimizerError::ScriptingError(e.to_string())
+        })?)),
+        other => Err(OptimizerError::ScriptingError(format!(
+            "unsupported language: {}",
+            other
+        ))),
+    }
+}
+
+/// Run `get_function_query` for `language_id` against `code` and return every matched
+/// function body.
+fn query_functions(code: &str, language_id: &str) -> Result<Vec<FunctionNode>, OptimizerError> {
+    let adapter = adapter_for(language_id)?;
+    let tree = adapter.parse(code);
+    let query = Query::new(adapter.get_grammar(), adapter.get_function_query())
+        .map_err(|e| OptimizerError::ScriptingError(e.to_string()))?;
+
+    let mut cursor = QueryCursor::new();
+    let matches = cursor.matches(&query, tree.root_node(), code.as_bytes());
+
+    let mut nodes = Vec::new();
+    for m in matches {
+        let node = match m.captures.first() {
+            Some(capture) => capture.node,
+            None => continue,
+        };
+        let name = node
+            .child_by_field_name("name")
+            .and_then(|n| n.utf8_text(code.as_bytes()).ok())
+            .unwrap_or("<anonymous>")
+            .to_string();
+        nodes.push(FunctionNode {
+            name,
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+            text: node.utf8_text(code.as_bytes()).unwrap_or("").to_string(),
+        });
+    }
+    Ok(nodes)
+}
+
+fn function_node_table<'lua>(lua: &'lua Lua, node: &FunctionNode) -> mlua::Result<Table<'lua>> {
+    let table = lua.create_table()?;
+    table.set("name", node.name.clone())?;
+    table.set("start_line", node.start_line)?;
+    table.set("end_line", node.end_line)?;
+    table.set("text", node.text.clone())?;
+    Ok(table)
+}
+
+fn hotspot_table<'lua>(lua: &'lua Lua, hotspot: &CodeHotspot) -> mlua::Result<Table<'lua>> {
+    let table = lua.create_table()?;
+    table.set("file_path", hotspot.file_path.clone())?;
+    table.set("line_start", hotspot.line_start)?;
+    table.set("line_end", hotspot.line_end)?;
+    table.set("energy_impact", hotspot.energy_impact)?;
+    table.set("description", hotspot.description.clone())?;
+    Ok(table)
+}
+
+fn parse_priority(value: &str) -> SuggestionPriority {
+    match value {
+        "high" => SuggestionPriority::High,
+        "low" => SuggestionPriority::Low,
+        _ => SuggestionPriority::Medium,
+    }
+}
+
+/// Convert a rule's returned table into an `OptimizationSuggestion`, folding its
+/// `rationale` into `description` (there's no separate rationale field to keep it in
+/// sync with), tagging it with the `hotspot` it was evaluated against so
+/// `suggestions::apply_suggestion` can locate it later, and defaulting any field the
+/// rule omitted.
+fn suggestion_from_table(
+    rule_name: &str,
+    table: Table,
+    hotspot: &CodeHotspot,
+) -> mlua::Result<OptimizationSuggestion> {
+    let description: String = table
+        .get::<_, String>("description")
+        .unwrap_or_else(|_| format!("{} suggestion", rule_name));
+    let rationale: Option<String> = table.get("rationale").ok();
+    let difficulty: String = table
+        .get::<_, String>("difficulty")
+        .unwrap_or_else(|_| "medium".to_string());
+    let code_snippet: String = table.get::<_, String>("code_snippet").unwrap_or_default();
+    let priority: Option<String> = table.get("priority").ok();
+    let estimated_joules: Option<f64> = table.get("estimated_joules").ok();
+
+    let description = match rationale {
+        Some(rationale) if !rationale.is_empty() => format!("{} ({})", description, rationale),
+        _ => description,
+    };
+
+    Ok(OptimizationSuggestion {
+        description,
+        impact: estimated_joules.unwrap_or(0.0),
+        difficulty,
+        code_snippet,
+        priority: priority.as_deref().map(parse_priority),
+        estimated_joules,
+        line_start: Some(hotspot.line_start),
+        line_end: Some(hotspot.line_end),
+    })
+}
+
+/// One loaded rule: a rule's own `Lua` state, isolated from every other rule's, so two
+/// scripts that both define a global `evaluate` (as every rule must) can't clobber each
+/// other.
+struct Rule {
+    name: String,
+    lua: Lua,
+}
+
+/// Loads and runs Lua optimization rules from a directory of `*.lua` files.
+pub struct RuleEngine {
+    rules: Vec<Rule>,
+}
+
+impl RuleEngine {
+    /// Load every `*.lua` file directly inside `rules_dir` as a rule, installing the
+    /// `codegreen.query_functions` host function in each rule's own Lua state before
+    /// running the script (so top-level script code, not just `evaluate`, can already
+    /// call it).
+    pub fn load_rules_dir(rules_dir: &Path) -> Result<Self, OptimizerError> {
+        let entries = fs::read_dir(rules_dir).map_err(|e| {
+            OptimizerError::ScriptingError(format!(
+                "failed to read rules directory {}: {}",
+                rules_dir.display(),
+                e
+            ))
+        })?;
+
+        let mut rules = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| OptimizerError::ScriptingError(e.to_string()))?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("lua") {
+                continue;
+            }
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("rule")
+                .to_string();
+            let source = fs::read_to_string(&path).map_err(|e| {
+                OptimizerError::ScriptingError(format!(
+                    "failed to read rule {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+
+            let lua = Lua::new();
+            Self::install_host_api(&lua).map_err(lua_err)?;
+            lua.load(&source)
+                .set_name(&name)
+                .exec()
+                .map_err(|e| OptimizerError::ScriptingError(format!("rule {}: {}", name, e)))?;
+
+            rules.push(Rule { name, lua });
+        }
+
+        Ok(Self { rules })
+    }
+
+    fn install_host_api(lua: &Lua) -> mlua::Result<()> {
+        let codegreen = lua.create_table()?;
+        let query_functions_fn =
+            lua.create_function(|lua, (code, language_id): (String, String)| {
+                let nodes = query_functions(&code, &language_id)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
+                let table = lua.create_table()?;
+                for (i, node) in nodes.iter().enumerate() {
+                    table.set(i + 1, function_node_table(lua, node)?)?;
+                }
+                Ok(table)
+            })?;
+        codegreen.set("query_functions", query_functions_fn)?;
+        lua.globals().set("codegreen", codegreen)?;
+        Ok(())
+    }
+
+    /// Run every loaded rule's `evaluate(nodes, hotspot)` against `code`/`language_id`'s
+    /// function bodies and each hotspot already found by the static analyzer,
+    /// collecting every non-nil suggestion a rule returns.
+    pub fn generate_suggestions(
+        &self,
+        code: &str,
+        language_id: &str,
+        analysis_result: &crate::analysis::AnalysisResult,
+    ) -> Result<Vec<OptimizationSuggestion>, OptimizerError> {
+        let nodes = query_functions(code, language_id)?;
+        let mut suggestions = Vec::new();
+
+        for rule in &self.rules {
+            let evaluate: mlua::Function = rule.lua.globals().get("evaluate").map_err(|e| {
+                OptimizerError::ScriptingError(format!(
+                    "rule {} has no `evaluate` function: {}",
+                    rule.name, e
+                ))
+            })?;
+
+            let nodes_table = rule.lua.create_table().map_err(lua_err)?;
+            for (i, node) in nodes.iter().enumerate() {
+                nodes_table
+                    .set(i + 1, function_node_table(&rule.lua, node).map_err(lua_err)?)
+                    .map_err(lua_err)?;
+            }
+
+            for hotspot in &analysis_result.hotspots {
+                let hotspot_table = hotspot_table(&rule.lua, hotspot).map_err(lua_err)?;
+                let result: Value = evaluate
+                    .call((nodes_table.clone(), hotspot_table))
+                    .map_err(|e| {
+                        OptimizerError::ScriptingError(format!(
+                            "rule {} failed: {}",
+                            rule.name, e
+                        ))
+                    })?;
+
+                if let Value::Table(table) = result {
+                    suggestions
+                        .push(suggestion_from_table(&rule.name, table, hotspot).map_err(lua_err)?);
+                }
+            }
+        }
+
+        Ok(suggestions)
+    }
+
+    /// Names of every rule loaded, in load order.
+    pub fn rule_names(&self) -> Vec<&str> {
+        self.rules.iter().map(|r| r.name.as_str()).collect()
+    }
+}
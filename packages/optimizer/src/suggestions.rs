@@ -2,6 +2,7 @@
 
 use crate::OptimizerError;
 use crate::OptimizationSuggestion;
+use crate::analysis::CodeHotspot;
 
 /// Represents the priority of an optimization suggestion
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,26 +12,108 @@ pub enum SuggestionPriority {
     Low,
 }
 
-/// Generate optimization suggestions based on analysis results
+/// Generate optimization suggestions from an analysis result. Each hotspot maps to a
+/// concrete rewrite, with its impact scaled by the code's overall complexity so
+/// hotspots found in already-dense code rank higher.
 pub fn generate_suggestions(
     code: &str,
     analysis_result: &crate::analysis::AnalysisResult,
 ) -> Result<Vec<OptimizationSuggestion>, OptimizerError> {
-    // TODO: Implement suggestion generation
-    Ok(Vec::new())
+    let _ = code; // suggestions are derived from the already-parsed hotspots
+    Ok(analysis_result
+        .hotspots
+        .iter()
+        .map(|hotspot| suggestion_for_hotspot(hotspot, analysis_result.complexity))
+        .collect())
 }
 
-/// Apply an optimization suggestion to the code
+fn suggestion_for_hotspot(hotspot: &CodeHotspot, complexity: f64) -> OptimizationSuggestion {
+    let rule_name = hotspot.description.split(':').next().unwrap_or("");
+    let (description, code_snippet, difficulty) = match rule_name {
+        "nested_loop" => (
+            "Hoist loop-invariant work out of the inner loop, or flatten the iteration into a single pass.",
+            "cache = [compute(x) for x in outer]\nfor value in cache:\n    ...",
+            "medium",
+        ),
+        "string_concat_in_loop" => (
+            "Collect the pieces in a buffer and join once after the loop instead of concatenating in place.",
+            "parts = []\nfor value in items:\n    parts.append(str(value))\nresult = \"\".join(parts)",
+            "easy",
+        ),
+        "busy_wait" => (
+            "Sleep or block on a condition/event between polls instead of spinning the CPU.",
+            "while not ready():\n    time.sleep(0.01)",
+            "easy",
+        ),
+        "sync_io_in_loop" => (
+            "Move blocking I/O outside the loop, or switch to a batched/async API.",
+            "with open(path) as f:\n    data = f.read()\nfor line in data.splitlines():\n    ...",
+            "hard",
+        ),
+        _ => (hotspot.description.as_str(), "", "medium"),
+    };
+
+    OptimizationSuggestion {
+        description: description.to_string(),
+        impact: hotspot.energy_impact * (1.0 + complexity * 0.01),
+        difficulty: difficulty.to_string(),
+        code_snippet: code_snippet.to_string(),
+        priority: None,
+        estimated_joules: None,
+        line_start: Some(hotspot.line_start),
+        line_end: Some(hotspot.line_end),
+    }
+}
+
+/// Priority bucket for a suggestion, based on its energy impact.
+pub fn priority_for_impact(impact: f64) -> SuggestionPriority {
+    if impact >= 4.0 {
+        SuggestionPriority::High
+    } else if impact >= 2.0 {
+        SuggestionPriority::Medium
+    } else {
+        SuggestionPriority::Low
+    }
+}
+
+/// Apply an optimization suggestion to the code by replacing the hotspot's line range
+/// (`suggestion.line_start..=line_end`, 1-indexed) with `code_snippet`. A suggestion
+/// with no recorded line range (or one that no longer fits the code it's applied
+/// against) can't be located, so those are rejected rather than silently applying
+/// nothing or guessing a location.
 pub fn apply_suggestion(
     code: &str,
     suggestion: &OptimizationSuggestion,
 ) -> Result<String, OptimizerError> {
-    // TODO: Implement suggestion application
-    Ok(code.to_string())
+    let (line_start, line_end) = match (suggestion.line_start, suggestion.line_end) {
+        (Some(start), Some(end)) => (start, end),
+        _ => {
+            return Err(OptimizerError::SuggestionError(
+                "suggestion has no recorded line range to apply".to_string(),
+            ))
+        }
+    };
+
+    let lines: Vec<&str> = code.lines().collect();
+    if line_start == 0 || line_start > line_end || line_end > lines.len() {
+        return Err(OptimizerError::SuggestionError(format!(
+            "suggestion line range {}..={} is out of bounds for {} line(s) of code",
+            line_start,
+            line_end,
+            lines.len()
+        )));
+    }
+
+    let mut result: Vec<&str> = Vec::with_capacity(lines.len());
+    result.extend_from_slice(&lines[..line_start - 1]);
+    result.extend(suggestion.code_snippet.lines());
+    result.extend_from_slice(&lines[line_end..]);
+    Ok(result.join("\n"))
 }
 
-/// Calculate the potential energy savings of a suggestion
+/// Calculate the potential energy savings of a suggestion. Prefers `estimated_joules`
+/// when a source provided one (e.g. a `scripting::RuleEngine` rule's own estimate),
+/// falling back to the static `impact` heuristic otherwise.
 pub fn calculate_savings(suggestion: &OptimizationSuggestion) -> f64 {
-    // TODO: Implement savings calculation
-    0.0
-} 
\ No newline at end of file
+    suggestion.estimated_joules.unwrap_or(suggestion.impact)
+}